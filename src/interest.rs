@@ -32,6 +32,9 @@ const WRITABLE: u8 = 0b0_010;
 const AIO: u8 = 0b0_100;
 #[cfg_attr(not(target_os = "freebsd"), allow(dead_code))]
 const LIO: u8 = 0b1_000;
+// Not readiness, but registration options.
+const ONESHOT: u8 = 0b01_0000;
+const LEVEL: u8 = 0b10_0000;
 
 impl Interest {
     /// Returns a `Interest` set representing readable interests.
@@ -53,6 +56,56 @@ impl Interest {
     #[cfg(target_os = "freebsd")]
     pub const LIO: Interest = Interest(unsafe { NonZeroU8::new_unchecked(LIO) });
 
+    /// Returns a `Interest` set representing a oneshot registration.
+    ///
+    /// `ONESHOT` is not a readiness by itself, it must be combined with other
+    /// interests, e.g. `Interest::READABLE | Interest::ONESHOT`. After an event
+    /// is returned for an event source registered with `ONESHOT` the source is
+    /// disarmed: no more events will be returned for it until it's rearmed
+    /// using [`Registry::rearm`] (or [`Registry::reregister`]). This can be
+    /// used to hand an event off to another thread, e.g. in a worker pool,
+    /// without receiving more events for the same source until the worker is
+    /// done with it.
+    ///
+    /// # Notes
+    ///
+    /// Using epoll (e.g. on Linux) the entire event source is disarmed. On
+    /// kqueue platforms (e.g. macOS) the readable and writable interests are
+    /// disarmed independently, i.e. receiving a readable event doesn't stop a
+    /// writable event from being returned.
+    ///
+    /// [`Registry::rearm`]: struct.Registry.html#method.rearm
+    /// [`Registry::reregister`]: struct.Registry.html#method.reregister
+    pub const ONESHOT: Interest = Interest(unsafe { NonZeroU8::new_unchecked(ONESHOT) });
+
+    /// Returns a `Interest` set representing a level-triggered registration.
+    ///
+    /// By default event sources are registered edge-triggered: an event is
+    /// only returned when the readiness of the source changes, which is why
+    /// the operations must be performed until they return a [`WouldBlock`]
+    /// error (see [draining readiness]). Registering with `LEVEL` (combined
+    /// with other interests, e.g. `Interest::READABLE | Interest::LEVEL`)
+    /// instead returns an event on every call to [`poll`] for as long as the
+    /// event source is ready. This allows, for example, reading a single frame
+    /// from a socket per event loop iteration.
+    ///
+    /// # Implementation notes
+    ///
+    /// Using epoll the source is registered without `EPOLLET` and using kqueue
+    /// the filters are registered without `EV_CLEAR`. On Windows the readiness
+    /// of the socket is polled again after each event, without waiting for an
+    /// operation to return a `WouldBlock` error.
+    ///
+    /// `LEVEL` can be combined with [`ONESHOT`], in which case the source is
+    /// disarmed after the first event and, once rearmed, returns an event if
+    /// it's still ready.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    /// [draining readiness]: struct.Poll.html#draining-readiness
+    /// [`poll`]: struct.Poll.html#method.poll
+    /// [`ONESHOT`]: #associatedconstant.ONESHOT
+    pub const LEVEL: Interest = Interest(unsafe { NonZeroU8::new_unchecked(LEVEL) });
+
     /// Add together two `Interest`.
     ///
     /// This does the same thing as the `BitOr` implementation, but is a
@@ -89,6 +142,17 @@ impl Interest {
     pub const fn is_lio(self) -> bool {
         (self.0.get() & LIO) != 0
     }
+
+    /// Returns true if `Interest` contains the oneshot registration option.
+    pub const fn is_oneshot(self) -> bool {
+        (self.0.get() & ONESHOT) != 0
+    }
+
+    /// Returns true if `Interest` contains the level-triggered registration
+    /// option.
+    pub const fn is_level(self) -> bool {
+        (self.0.get() & LEVEL) != 0
+    }
 }
 
 impl ops::BitOr for Interest {
@@ -149,6 +213,20 @@ impl fmt::Debug for Interest {
                 one = true
             }
         }
+        if self.is_oneshot() {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "ONESHOT")?;
+            one = true
+        }
+        if self.is_level() {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "LEVEL")?;
+            one = true
+        }
         debug_assert!(one, "printing empty interests");
         Ok(())
     }
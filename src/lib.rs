@@ -87,6 +87,16 @@ pub use waker::Waker;
 pub mod unix {
     //! Unix only extensions.
     pub use crate::sys::SourceFd;
+
+    cfg_os_poll! {
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        pub use crate::sys::{
+            Clock, Inotify, InotifyEvent, Process, Timer, WatchDescriptor, WatchEvent,
+        };
+
+        pub use crate::sys::pipe;
+        pub use crate::sys::{Pending, SigSet, SignalInfo, Signals};
+    }
 }
 
 #[cfg(all(windows, feature = "os-util"))]
@@ -119,7 +129,8 @@ pub mod features {
     #![cfg_attr(not(feature = "os-util"), doc = "## `os-util` (disabled)")]
     //!
     //! `os-util` enables additional OS specific facilities. Currently this
-    //! means the `unix` module (with `SourceFd`) becomes available.
+    //! means the `unix` module (with `SourceFd`, `pipe`, `Signals`, and
+    //! `Inotify`, `Process` and `Timer` on Linux) becomes available.
     //!
     //! ## Network types
     //!
@@ -127,7 +138,7 @@ pub mod features {
     //!
     #![cfg_attr(feature = "tcp", doc = "* `tcp` (enabled)")]
     #![cfg_attr(not(feature = "tcp"), doc = "* `tcp` (disabled)")]
-    //! : includes `TcpStream` and `TcpListener`,
+    //! : includes `TcpStream`, `TcpListener` and `TcpSocket`,
     #![cfg_attr(feature = "udp", doc = "* `udp` (enabled)")]
     #![cfg_attr(not(feature = "udp"), doc = "* `udp` (disabled)")]
     //! : includes `UdpSocket`, and
@@ -135,6 +146,15 @@ pub mod features {
     #![cfg_attr(not(feature = "uds"), doc = "* `uds` (disabled)")]
     //! : includes `UnixDatagram`, `UnixListener`, `UnixStream` and `SocketAddr`.
     //!
+    //! On Linux and Android `tcp` and `udp` also include `ReusePortBuilder`,
+    //! to create groups of sockets sharing an address.
+    //!
+    //! On Linux and Android `udp` also includes `IcmpSocket`, for unprivileged
+    //! ICMP echo ("ping") sockets.
+    //!
+    //! On platforms other than macOS and iOS `uds` also includes
+    //! `UnixSeqpacketListener` and `UnixSeqpacket`.
+    //!
     //! All types can be found in the `net` module.
     //!
     #![cfg_attr(feature = "extra-docs", doc = "## `extra-docs` (enabled)")]
@@ -0,0 +1,96 @@
+//! Types used to send and receive batches of datagrams.
+
+/// A datagram to send as part of a batch.
+///
+/// Used by `UdpSocket::send_batch` and `UnixDatagram::send_batch`, where `A`
+/// is the type of address of the socket.
+#[derive(Copy, Clone, Debug)]
+pub struct SendMsg<'a, A> {
+    pub(crate) buf: &'a [u8],
+    pub(crate) addr: Option<A>,
+}
+
+impl<'a, A> SendMsg<'a, A> {
+    /// Create a new datagram containing `buf`, sent to the address the socket
+    /// is connected to.
+    pub fn new(buf: &'a [u8]) -> SendMsg<'a, A> {
+        SendMsg { buf, addr: None }
+    }
+
+    /// Create a new datagram containing `buf`, sent to `addr`.
+    pub fn to(buf: &'a [u8], addr: A) -> SendMsg<'a, A> {
+        SendMsg {
+            buf,
+            addr: Some(addr),
+        }
+    }
+
+    /// Returns the data to send.
+    pub fn buf(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// Returns the address the datagram is sent to, if any.
+    pub fn addr(&self) -> Option<&A> {
+        self.addr.as_ref()
+    }
+}
+
+/// A buffer to receive a datagram into as part of a batch.
+///
+/// Used by `UdpSocket::recv_batch` and `UnixDatagram::recv_batch`, where `A`
+/// is the type of address of the socket. After a successful call the first
+/// `n` messages, where `n` is the returned count, hold the received
+/// datagrams.
+#[derive(Debug)]
+pub struct RecvMsg<'a, A> {
+    pub(crate) buf: &'a mut [u8],
+    pub(crate) len: usize,
+    pub(crate) addr: Option<A>,
+    pub(crate) truncated: bool,
+}
+
+impl<'a, A> RecvMsg<'a, A> {
+    /// Create a new message that receives a datagram into `buf`.
+    pub fn new(buf: &'a mut [u8]) -> RecvMsg<'a, A> {
+        RecvMsg {
+            buf,
+            len: 0,
+            addr: None,
+            truncated: false,
+        }
+    }
+
+    /// Returns the received data.
+    pub fn data(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the number of bytes received.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes were received.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the address of the sender.
+    ///
+    /// This is `None` if no datagram was received into this message.
+    pub fn addr(&self) -> Option<&A> {
+        self.addr.as_ref()
+    }
+
+    /// Returns `true` if the datagram was larger than the buffer, in which
+    /// case the excess data is lost.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns the buffer, e.g. to reuse it for another batch.
+    pub fn into_buf(self) -> &'a mut [u8] {
+        self.buf
+    }
+}
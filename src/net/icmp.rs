@@ -0,0 +1,342 @@
+//! Primitives for working with ICMP echo ("ping") sockets.
+
+use crate::io_source::IoSource;
+use crate::{event, sys, Interest, Registry, Token};
+
+use std::fmt;
+use std::io;
+use std::net::{self, SocketAddr};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+/// Length of the header of an ICMP and ICMPv6 echo request or reply.
+const ECHO_HEADER_LEN: usize = 8;
+
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+const ICMPV6_ECHO_REPLY: u8 = 129;
+
+/// An unprivileged ICMP or ICMPv6 echo socket, also known as a ping socket.
+///
+/// This is a datagram socket that can send ICMP echo requests and receive
+/// the matching echo replies without the privileges required for raw
+/// sockets. Use [`IcmpEcho`] to encode the requests and decode the replies.
+///
+/// The kernel only allows processes whose group is in the range set by the
+/// `net.ipv4.ping_group_range` sysctl to create these sockets, for both IPv4
+/// and IPv6. Creating the socket fails with a permission denied error
+/// otherwise.
+///
+/// The kernel uses the port of the socket's local address as the identifier
+/// of all echo requests sent on the socket, overwriting the identifier in
+/// the request, and only delivers replies with that identifier to the socket.
+///
+/// Only available on Linux and Android.
+///
+/// [`IcmpEcho`]: struct.IcmpEcho.html
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{IcmpEcho, IcmpSocket};
+///
+/// let socket = match IcmpSocket::bind("0.0.0.0:0".parse()?) {
+///     Ok(socket) => socket,
+///     // Not allowed by `net.ipv4.ping_group_range`.
+///     Err(ref err) if err.kind() == std::io::ErrorKind::PermissionDenied => return Ok(()),
+///     Err(err) => return Err(err.into()),
+/// };
+///
+/// let mut buf = [0; 64];
+/// let n = IcmpEcho::request(1, b"ping").encode_v4(&mut buf)?;
+/// socket.send_to(&buf[..n], "127.0.0.1:0".parse()?)?;
+///
+/// // We must check if the socket is readable before calling recv_from,
+/// // or we could run into a WouldBlock error.
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub struct IcmpSocket {
+    inner: IoSource<net::UdpSocket>,
+}
+
+impl IcmpSocket {
+    /// Creates an ICMP echo socket bound to `addr`.
+    ///
+    /// An IPv4 address creates an ICMP socket, an IPv6 address an ICMPv6
+    /// socket. The port of `addr` is used as the identifier of the echo
+    /// requests sent on the socket, if it's zero the kernel picks an unused
+    /// identifier.
+    pub fn bind(addr: SocketAddr) -> io::Result<IcmpSocket> {
+        sys::icmp::bind(addr).map(|socket| IcmpSocket {
+            inner: IoSource::new(socket),
+        })
+    }
+
+    /// Returns the socket address that this socket was bound to.
+    ///
+    /// The port of the address is the identifier of the echo requests sent
+    /// on the socket.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Sends the echo request in `buf` to the host at `target`.
+    ///
+    /// `buf` must contain an encoded echo request of the socket's address
+    /// family, see [`IcmpEcho::encode_v4`] and [`IcmpEcho::encode_v6`]. The
+    /// port of `target` is ignored, the scope id of an IPv6 address is used
+    /// to pick the interface for link-local addresses. Returns the number of
+    /// bytes sent.
+    ///
+    /// [`IcmpEcho::encode_v4`]: struct.IcmpEcho.html#method.encode_v4
+    /// [`IcmpEcho::encode_v6`]: struct.IcmpEcho.html#method.encode_v6
+    pub fn send_to(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize> {
+        self.inner.do_io(|inner| inner.send_to(buf, target))
+    }
+
+    /// Receives an echo reply into `buf`.
+    ///
+    /// On success, returns the number of bytes read and the address of the
+    /// host that sent the reply, its port is always zero. The reply starts
+    /// with the ICMP header, the IP header is not included, and can be
+    /// decoded with [`IcmpEcho::decode_v4`] or [`IcmpEcho::decode_v6`].
+    ///
+    /// [`IcmpEcho::decode_v4`]: struct.IcmpEcho.html#method.decode_v4
+    /// [`IcmpEcho::decode_v6`]: struct.IcmpEcho.html#method.decode_v6
+    pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        self.inner.do_io(|inner| inner.recv_from(buf))
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+}
+
+impl event::Source for IcmpSocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+impl fmt::Debug for IcmpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IcmpSocket")
+            .field("fd", &self.as_raw_fd())
+            .finish()
+    }
+}
+
+impl IntoRawFd for IcmpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl AsRawFd for IcmpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl FromRawFd for IcmpSocket {
+    /// Converts a `RawFd` to an `IcmpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is an ICMP
+    /// echo socket in non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> IcmpSocket {
+        IcmpSocket {
+            inner: IoSource::new(net::UdpSocket::from_raw_fd(fd)),
+        }
+    }
+}
+
+/// Whether an [`IcmpEcho`] is a request or a reply.
+///
+/// [`IcmpEcho`]: struct.IcmpEcho.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IcmpEchoKind {
+    /// An echo request, sent by ping.
+    Request,
+    /// An echo reply, sent in response to a request.
+    Reply,
+}
+
+/// An ICMP or ICMPv6 echo request or reply.
+///
+/// The same message is used for both protocols, only the message types
+/// differ. Use the `_v4` methods for ICMP and the `_v6` methods for ICMPv6.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IcmpEcho<'a> {
+    /// Whether this is a request or a reply.
+    pub kind: IcmpEchoKind,
+    /// Identifier used to match replies to requests.
+    ///
+    /// Overwritten by the kernel for requests sent on an [`IcmpSocket`].
+    ///
+    /// [`IcmpSocket`]: struct.IcmpSocket.html
+    pub identifier: u16,
+    /// Sequence number used to match replies to requests.
+    pub sequence: u16,
+    /// Data that is echoed back in the reply.
+    pub payload: &'a [u8],
+}
+
+impl<'a> IcmpEcho<'a> {
+    /// Creates an echo request with `sequence` and `payload`, and an
+    /// identifier of zero.
+    pub fn request(sequence: u16, payload: &'a [u8]) -> IcmpEcho<'a> {
+        IcmpEcho {
+            kind: IcmpEchoKind::Request,
+            identifier: 0,
+            sequence,
+            payload,
+        }
+    }
+
+    /// Returns the length of the encoded message.
+    pub fn encoded_len(&self) -> usize {
+        ECHO_HEADER_LEN + self.payload.len()
+    }
+
+    /// Encodes the message as an ICMP (IPv4) echo into `buf`, including the
+    /// checksum.
+    ///
+    /// Returns the length of the encoded message, or an error if `buf` is
+    /// smaller than [`encoded_len`].
+    ///
+    /// [`encoded_len`]: #method.encoded_len
+    pub fn encode_v4(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let message_type = match self.kind {
+            IcmpEchoKind::Request => ICMP_ECHO_REQUEST,
+            IcmpEchoKind::Reply => ICMP_ECHO_REPLY,
+        };
+        let n = self.encode(message_type, buf)?;
+        let checksum = checksum(&buf[..n]);
+        buf[2..4].copy_from_slice(&checksum.to_be_bytes());
+        Ok(n)
+    }
+
+    /// Encodes the message as an ICMPv6 echo into `buf`.
+    ///
+    /// The checksum is left zero, as the ICMPv6 checksum covers the IPv6
+    /// source and destination addresses it's calculated by the kernel.
+    ///
+    /// Returns the length of the encoded message, or an error if `buf` is
+    /// smaller than [`encoded_len`].
+    ///
+    /// [`encoded_len`]: #method.encoded_len
+    pub fn encode_v6(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let message_type = match self.kind {
+            IcmpEchoKind::Request => ICMPV6_ECHO_REQUEST,
+            IcmpEchoKind::Reply => ICMPV6_ECHO_REPLY,
+        };
+        self.encode(message_type, buf)
+    }
+
+    fn encode(&self, message_type: u8, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.encoded_len();
+        if buf.len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "buffer too small for ICMP echo message",
+            ));
+        }
+
+        buf[0] = message_type;
+        buf[1] = 0; // Code.
+        buf[2..4].copy_from_slice(&[0, 0]); // Checksum.
+        buf[4..6].copy_from_slice(&self.identifier.to_be_bytes());
+        buf[6..8].copy_from_slice(&self.sequence.to_be_bytes());
+        buf[ECHO_HEADER_LEN..len].copy_from_slice(self.payload);
+        Ok(len)
+    }
+
+    /// Decodes an ICMP (IPv4) echo request or reply from `packet`, as
+    /// received by [`IcmpSocket::recv_from`].
+    ///
+    /// Returns an error if `packet` isn't an echo message. The checksum is
+    /// not verified, the kernel already does so for received messages.
+    ///
+    /// [`IcmpSocket::recv_from`]: struct.IcmpSocket.html#method.recv_from
+    pub fn decode_v4(packet: &'a [u8]) -> io::Result<IcmpEcho<'a>> {
+        IcmpEcho::decode(packet, ICMP_ECHO_REQUEST, ICMP_ECHO_REPLY)
+    }
+
+    /// Decodes an ICMPv6 echo request or reply from `packet`, as received
+    /// by [`IcmpSocket::recv_from`].
+    ///
+    /// Returns an error if `packet` isn't an echo message. The checksum is
+    /// not verified, the kernel already does so for received messages.
+    ///
+    /// [`IcmpSocket::recv_from`]: struct.IcmpSocket.html#method.recv_from
+    pub fn decode_v6(packet: &'a [u8]) -> io::Result<IcmpEcho<'a>> {
+        IcmpEcho::decode(packet, ICMPV6_ECHO_REQUEST, ICMPV6_ECHO_REPLY)
+    }
+
+    fn decode(packet: &'a [u8], request: u8, reply: u8) -> io::Result<IcmpEcho<'a>> {
+        if packet.len() < ECHO_HEADER_LEN || packet[1] != 0 {
+            return Err(invalid_echo());
+        }
+        let kind = if packet[0] == request {
+            IcmpEchoKind::Request
+        } else if packet[0] == reply {
+            IcmpEchoKind::Reply
+        } else {
+            return Err(invalid_echo());
+        };
+
+        Ok(IcmpEcho {
+            kind,
+            identifier: u16::from_be_bytes([packet[4], packet[5]]),
+            sequence: u16::from_be_bytes([packet[6], packet[7]]),
+            payload: &packet[ECHO_HEADER_LEN..],
+        })
+    }
+}
+
+fn invalid_echo() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "not an ICMP echo message")
+}
+
+/// The Internet checksum (RFC 1071) of `data`.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u64 = 0;
+    for chunk in data.chunks(2) {
+        let word = if chunk.len() == 2 {
+            u16::from_be_bytes([chunk[0], chunk[1]])
+        } else {
+            u16::from_be_bytes([chunk[0], 0])
+        };
+        sum += u64::from(word);
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
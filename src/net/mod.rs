@@ -9,16 +9,57 @@
 
 cfg_tcp! {
     mod tcp;
-    pub use self::tcp::{TcpListener, TcpStream};
+    pub use self::tcp::{ConnectStatus, TcpListener, TcpSocket, TcpStream};
 }
 
 cfg_udp! {
     mod udp;
     pub use self::udp::UdpSocket;
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub use self::udp::PacketInfo;
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+cfg_udp! {
+    mod icmp;
+    pub use self::icmp::{IcmpEcho, IcmpEchoKind, IcmpSocket};
+}
+
+#[cfg(all(unix, any(feature = "udp", feature = "uds")))]
+mod batch;
+#[cfg(all(unix, any(feature = "udp", feature = "uds")))]
+pub use self::batch::{RecvMsg, SendMsg};
+
+#[cfg(all(
+    any(feature = "tcp", feature = "udp"),
+    any(target_os = "android", target_os = "linux")
+))]
+mod timestamp;
+#[cfg(all(
+    any(feature = "tcp", feature = "udp"),
+    any(target_os = "android", target_os = "linux")
+))]
+pub use self::timestamp::TxTimestamp;
+
+#[cfg(all(
+    feature = "os-poll",
+    any(feature = "tcp", feature = "udp"),
+    any(target_os = "android", target_os = "linux")
+))]
+mod reuseport;
+#[cfg(all(
+    feature = "os-poll",
+    any(feature = "tcp", feature = "udp"),
+    any(target_os = "android", target_os = "linux")
+))]
+pub use self::reuseport::{ReusePortBuilder, ReusePortGroup, ReusePortMember};
+
 #[cfg(unix)]
 cfg_uds! {
     mod uds;
-    pub use self::uds::{SocketAddr, UnixDatagram, UnixListener, UnixStream};
+    pub use self::uds::{
+        ReceivedFd, ReceivedFds, SocketAddr, UCred, UnixDatagram, UnixListener, UnixStream,
+    };
+    #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+    pub use self::uds::{UnixSeqpacket, UnixSeqpacketListener};
 }
@@ -0,0 +1,274 @@
+//! Groups of sockets sharing an address using `SO_REUSEPORT`.
+
+use std::io;
+use std::net::SocketAddr;
+use std::os::unix::io::AsRawFd;
+use std::slice;
+
+#[cfg(feature = "udp")]
+use crate::net::UdpSocket;
+#[cfg(feature = "tcp")]
+use crate::net::{TcpListener, TcpSocket};
+use crate::sys;
+
+/// A builder for a group of sockets bound to the same address using
+/// `SO_REUSEPORT`.
+///
+/// The kernel load balances incoming connections (for [`TcpListener`]s) or
+/// datagrams (for [`UdpSocket`]s) between all sockets in the group. This
+/// allows running one [`Poll`] instance per thread, each with its own socket,
+/// without sharing a single listener between the threads.
+///
+/// By default the kernel selects a socket based on a hash of the connection's
+/// addresses. Using [`steer_by_cpu`] a classic BPF program is attached to the
+/// group (`SO_ATTACH_REUSEPORT_CBPF`) which instead selects the socket based
+/// on the CPU that received the packet: socket `n` of a group of `size`
+/// sockets receives everything handled by the CPUs `c` where
+/// `c % size == n`. Each socket also gets its `SO_INCOMING_CPU` option set to
+/// `n`. Pinning the thread that polls socket `n` to CPU `n` keeps all the
+/// processing of a connection on a single CPU.
+///
+/// This is only available on Linux and Android.
+///
+/// [`TcpListener`]: struct.TcpListener.html
+/// [`UdpSocket`]: struct.UdpSocket.html
+/// [`Poll`]: ../struct.Poll.html
+/// [`steer_by_cpu`]: #method.steer_by_cpu
+///
+/// # Examples
+///
+#[cfg_attr(feature = "tcp", doc = "```")]
+#[cfg_attr(not(feature = "tcp"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use mio::net::ReusePortBuilder;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let group = ReusePortBuilder::new(4)
+///     .steer_by_cpu(true)
+///     .bind_tcp("127.0.0.1:0".parse()?)?;
+/// println!("listening on {}", group.local_addr());
+///
+/// let handles: Vec<_> = group.into_members().into_iter().map(|member| {
+///     thread::spawn(move || -> std::io::Result<()> {
+///         // Pin this thread to `member.cpu()` here, so that it accepts the
+///         // connections handled by that CPU.
+///         let mut listener = member.into_socket();
+///
+///         let mut poll = Poll::new()?;
+///         let mut events = Events::with_capacity(128);
+///         poll.registry().register(&mut listener, Token(0), Interest::READABLE)?;
+///
+///         poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+///         // Accept connections...
+///         Ok(())
+///     })
+/// }).collect();
+/// # for handle in handles {
+/// #     handle.join().unwrap()?;
+/// # }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReusePortBuilder {
+    size: usize,
+    steer_by_cpu: bool,
+    #[cfg(feature = "tcp")]
+    backlog: u32,
+}
+
+impl ReusePortBuilder {
+    /// Create a new builder for a group of `size` sockets.
+    pub fn new(size: usize) -> ReusePortBuilder {
+        ReusePortBuilder {
+            size,
+            steer_by_cpu: false,
+            #[cfg(feature = "tcp")]
+            backlog: 1024,
+        }
+    }
+
+    /// Select the socket in the group based on the CPU that received the
+    /// packet, rather than a hash of the addresses.
+    ///
+    /// Defaults to false.
+    pub fn steer_by_cpu(&mut self, steer: bool) -> &mut ReusePortBuilder {
+        self.steer_by_cpu = steer;
+        self
+    }
+
+    /// Sets the backlog passed to `listen(2)` for each listener created by
+    /// [`bind_tcp`].
+    ///
+    /// Defaults to 1024.
+    ///
+    /// [`bind_tcp`]: #method.bind_tcp
+    #[cfg(feature = "tcp")]
+    pub fn backlog(&mut self, backlog: u32) -> &mut ReusePortBuilder {
+        self.backlog = backlog;
+        self
+    }
+
+    /// Create the group of TCP listeners bound to `addr`.
+    ///
+    /// If the port of `addr` is zero the first listener will pick a port,
+    /// which the remaining listeners will share.
+    #[cfg(feature = "tcp")]
+    pub fn bind_tcp(&self, addr: SocketAddr) -> io::Result<ReusePortGroup<TcpListener>> {
+        self.bind(
+            addr,
+            |addr, cpu| {
+                let socket = TcpSocket::new_for_addr(addr)?;
+                socket.set_reuseaddr(true)?;
+                socket.set_reuseport(true)?;
+                if let Some(cpu) = cpu {
+                    sys::reuseport::set_incoming_cpu(socket.as_raw_fd(), cpu)?;
+                }
+                socket.bind(addr)?;
+                socket.listen(self.backlog)
+            },
+            TcpListener::local_addr,
+        )
+    }
+
+    /// Create the group of UDP sockets bound to `addr`.
+    ///
+    /// If the port of `addr` is zero the first socket will pick a port, which
+    /// the remaining sockets will share.
+    #[cfg(feature = "udp")]
+    pub fn bind_udp(&self, addr: SocketAddr) -> io::Result<ReusePortGroup<UdpSocket>> {
+        self.bind(
+            addr,
+            |addr, cpu| sys::udp::bind_reuseport(addr, cpu).map(UdpSocket::from_std),
+            UdpSocket::local_addr,
+        )
+    }
+
+    fn bind<S, B, L>(
+        &self,
+        mut addr: SocketAddr,
+        bind: B,
+        local_addr: L,
+    ) -> io::Result<ReusePortGroup<S>>
+    where
+        S: AsRawFd,
+        B: Fn(SocketAddr, Option<usize>) -> io::Result<S>,
+        L: Fn(&S) -> io::Result<SocketAddr>,
+    {
+        if self.size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reuseport group must contain at least one socket",
+            ));
+        }
+
+        let mut members = Vec::with_capacity(self.size);
+        for n in 0..self.size {
+            let cpu = if self.steer_by_cpu { Some(n) } else { None };
+            let socket = bind(addr, cpu)?;
+            if n == 0 {
+                // Use the actual port (in case it was zero) for the remaining
+                // sockets.
+                addr = local_addr(&socket)?;
+            }
+            members.push(ReusePortMember { socket, cpu });
+        }
+
+        // The program is shared by all sockets in the group, so attaching it
+        // to any socket will do.
+        if self.steer_by_cpu {
+            sys::reuseport::attach_cpu_steering(members[0].socket.as_raw_fd(), self.size)?;
+        }
+
+        Ok(ReusePortGroup {
+            local_addr: addr,
+            members,
+        })
+    }
+}
+
+/// A group of sockets bound to the same address, created by
+/// [`ReusePortBuilder`].
+///
+/// [`ReusePortBuilder`]: struct.ReusePortBuilder.html
+#[derive(Debug)]
+pub struct ReusePortGroup<S> {
+    local_addr: SocketAddr,
+    members: Vec<ReusePortMember<S>>,
+}
+
+impl<S> ReusePortGroup<S> {
+    /// Returns the local address all sockets in the group are bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the number of sockets in the group.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the group contains no sockets.
+    ///
+    /// Always false for a group created by [`ReusePortBuilder`].
+    ///
+    /// [`ReusePortBuilder`]: struct.ReusePortBuilder.html
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns an iterator over the members of the group, in the order the
+    /// sockets joined the group.
+    pub fn iter(&self) -> slice::Iter<'_, ReusePortMember<S>> {
+        self.members.iter()
+    }
+
+    /// Returns an iterator that allows modifying each member of the group.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, ReusePortMember<S>> {
+        self.members.iter_mut()
+    }
+
+    /// Consumes the group, returning its members so they can be moved to the
+    /// threads polling them.
+    pub fn into_members(self) -> Vec<ReusePortMember<S>> {
+        self.members
+    }
+}
+
+/// A socket in a [`ReusePortGroup`].
+///
+/// [`ReusePortGroup`]: struct.ReusePortGroup.html
+#[derive(Debug)]
+pub struct ReusePortMember<S> {
+    socket: S,
+    cpu: Option<usize>,
+}
+
+impl<S> ReusePortMember<S> {
+    /// Returns the CPU this socket serves, if the group is steered by CPU.
+    ///
+    /// This socket also serves all CPUs `c` where `c % group.len()` equals
+    /// the returned CPU.
+    pub fn cpu(&self) -> Option<usize> {
+        self.cpu
+    }
+
+    /// Returns a reference to the socket.
+    pub fn socket(&self) -> &S {
+        &self.socket
+    }
+
+    /// Returns a mutable reference to the socket, e.g. to register it.
+    pub fn socket_mut(&mut self) -> &mut S {
+        &mut self.socket
+    }
+
+    /// Consumes the member, returning the socket.
+    pub fn into_socket(self) -> S {
+        self.socket
+    }
+}
@@ -3,9 +3,11 @@ use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+use std::time::Duration;
 use std::{fmt, io};
 
-use super::TcpStream;
+use super::{TcpSocket, TcpStream};
 use crate::io_source::IoSource;
 use crate::{event, sys, Interest, Registry, Token};
 
@@ -48,8 +50,20 @@ impl TcpListener {
     /// 2. Set the `SO_REUSEADDR` option on the socket on Unix.
     /// 3. Bind the socket to the specified address.
     /// 4. Calls `listen` on the socket to prepare it to receive new connections.
+    ///
+    /// Use [`TcpSocket`] to configure the socket before binding, for example
+    /// to pick a different backlog.
+    ///
+    /// [`TcpSocket`]: struct.TcpSocket.html
     pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
-        sys::tcp::bind(addr).map(TcpListener::from_std)
+        let socket = TcpSocket::new_for_addr(addr)?;
+
+        // Set SO_REUSEADDR (mirrors what libstd does).
+        #[cfg(unix)]
+        socket.set_reuseaddr(true)?;
+
+        socket.bind(addr)?;
+        socket.listen(1024)
     }
 
     /// Creates a new `TcpListener` from a standard `net::TcpListener`.
@@ -100,6 +114,175 @@ impl TcpListener {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If enabled, the operating system periodically sends keepalive probes
+    /// on an idle connection. If the peer doesn't respond to the probes the
+    /// connection is closed. The socket then reports an error event, and
+    /// reading or writing returns an error (usually `TimedOut`).
+    ///
+    /// See [`set_keepalive_time`], [`set_keepalive_interval`] and
+    /// [`set_keepalive_retries`] to configure the probes.
+    ///
+    /// On most platforms streams accepted from this listener inherit the
+    /// option.
+    ///
+    /// [`set_keepalive_time`]: #method.set_keepalive_time
+    /// [`set_keepalive_interval`]: #method.set_keepalive_interval
+    /// [`set_keepalive_retries`]: #method.set_keepalive_retries
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        sys::tcp::set_keepalive(self.as_socket(), keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`set_keepalive`][link].
+    ///
+    /// [link]: #method.set_keepalive
+    pub fn keepalive(&self) -> io::Result<bool> {
+        sys::tcp::get_keepalive(self.as_socket())
+    }
+
+    /// Sets the amount of time a connection accepted from this listener must
+    /// be idle before the first keepalive probe is sent.
+    ///
+    /// This sets the `TCP_KEEPIDLE` option (`TCP_KEEPALIVE` on macOS and iOS).
+    /// The time is rounded down to whole seconds. The listener itself never
+    /// sends keepalive probes, on most platforms streams accepted from it
+    /// inherit the option.
+    ///
+    /// # Notes
+    ///
+    /// Not available on OpenBSD, Solaris and illumos. On Windows this
+    /// requires Windows 10, version 1709 or later.
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        sys::tcp::set_keepalive_time(self.as_socket(), time)
+    }
+
+    /// Gets the amount of time a connection accepted from this listener must
+    /// be idle before the first keepalive probe is sent.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_time`][link].
+    ///
+    /// [link]: #method.set_keepalive_time
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        sys::tcp::get_keepalive_time(self.as_socket())
+    }
+
+    /// Sets the time between keepalive probes, if the previous probe wasn't
+    /// acknowledged, for connections accepted from this listener.
+    ///
+    /// This sets the `TCP_KEEPINTVL` option. The interval is rounded down to
+    /// whole seconds. On most platforms streams accepted from this listener
+    /// inherit the option.
+    ///
+    /// # Notes
+    ///
+    /// Not available on OpenBSD, Solaris and illumos. On Windows this
+    /// requires Windows 10, version 1709 or later.
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        sys::tcp::set_keepalive_interval(self.as_socket(), interval)
+    }
+
+    /// Gets the time between keepalive probes for connections accepted from
+    /// this listener.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_interval`][link].
+    ///
+    /// [link]: #method.set_keepalive_interval
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        sys::tcp::get_keepalive_interval(self.as_socket())
+    }
+
+    /// Sets the number of unacknowledged keepalive probes sent before a
+    /// connection accepted from this listener is considered dead.
+    ///
+    /// This sets the `TCP_KEEPCNT` option. On most platforms streams accepted
+    /// from this listener inherit the option.
+    ///
+    /// # Notes
+    ///
+    /// Not available on OpenBSD, Solaris and illumos. On Windows this
+    /// requires Windows 10, version 1709 or later.
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        sys::tcp::set_keepalive_retries(self.as_socket(), retries)
+    }
+
+    /// Gets the number of unacknowledged keepalive probes sent before a
+    /// connection accepted from this listener is considered dead.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_retries`][link].
+    ///
+    /// [link]: #method.set_keepalive_retries
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        sys::tcp::get_keepalive_retries(self.as_socket())
+    }
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time transmitted data may remain
+    /// unacknowledged before the connection is closed, which also limits the
+    /// time spent on keepalive probes. `None` restores the system default.
+    /// The timeout is rounded down to whole milliseconds.
+    ///
+    /// Streams accepted from this listener inherit the option.
+    ///
+    /// Only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        sys::tcp::set_user_timeout(self.as_socket(), timeout)
+    }
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_user_timeout`][link].
+    ///
+    /// [link]: #method.set_user_timeout
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        sys::tcp::get_user_timeout(self.as_socket())
+    }
+
+    /// Enables TCP Fast Open on this listener by setting the `TCP_FASTOPEN`
+    /// option.
+    ///
+    /// `queue_len` is the maximum number of pending Fast Open requests, i.e.
+    /// connections whose SYN carried data but which haven't been accepted
+    /// yet. Zero disables Fast Open. Clients can then send data along with
+    /// the SYN, see [`TcpStream::connect_with_data`], saving a round trip.
+    ///
+    /// Fast Open must also be enabled for servers in the kernel, by setting
+    /// bit `0x2` of the `net.ipv4.tcp_fastopen` sysctl. Otherwise this
+    /// succeeds, but connections fall back to a regular handshake.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`TcpStream::connect_with_data`]: struct.TcpStream.html#method.connect_with_data
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_fastopen(&self, queue_len: u32) -> io::Result<()> {
+        sys::tcp::set_fastopen(self.as_socket(), queue_len)
+    }
+
+    /// Gets the value of the `TCP_FASTOPEN` option on this socket.
+    ///
+    /// For more information about this option, see [`set_fastopen`][link].
+    ///
+    /// [link]: #method.set_fastopen
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn fastopen(&self) -> io::Result<u32> {
+        sys::tcp::get_fastopen(self.as_socket())
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -108,6 +291,16 @@ impl TcpListener {
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    #[cfg(unix)]
+    fn as_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_fd()
+    }
+
+    #[cfg(windows)]
+    fn as_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_socket() as sys::tcp::TcpSocket
+    }
 }
 
 impl event::Source for TcpListener {
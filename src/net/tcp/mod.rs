@@ -2,4 +2,7 @@ mod listener;
 pub use self::listener::TcpListener;
 
 mod stream;
-pub use self::stream::TcpStream;
+pub use self::stream::{ConnectStatus, TcpStream};
+
+mod socket;
+pub use self::socket::TcpSocket;
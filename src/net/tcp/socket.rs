@@ -0,0 +1,289 @@
+use std::io;
+use std::mem;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+
+use crate::net::{TcpListener, TcpStream};
+use crate::sys;
+
+/// A non-blocking TCP socket used to configure a stream or listener before it
+/// is connected or bound.
+///
+/// `TcpSocket` wraps an operating system socket and enables the caller to
+/// configure the socket before establishing a TCP connection or accepting
+/// inbound connections. The caller is able to set socket options (e.g. the
+/// buffer sizes or `SO_REUSEPORT`), bind to a specific local address and pick
+/// the backlog passed to `listen`. [`TcpListener::bind`] and
+/// [`TcpStream::connect`] are convenience methods built on top of this type.
+///
+/// The socket is created in non-blocking mode with the close-on-exec flag set,
+/// and will be closed when the value is dropped.
+///
+/// [`TcpListener::bind`]: struct.TcpListener.html#method.bind
+/// [`TcpStream::connect`]: struct.TcpStream.html#method.connect
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpSocket;
+///
+/// let addr = "127.0.0.1:0".parse()?;
+///
+/// let socket = TcpSocket::new_v4()?;
+/// socket.set_reuseaddr(true)?;
+/// socket.set_recv_buffer_size(64 * 1024)?;
+/// socket.bind(addr)?;
+///
+/// let listener = socket.listen(128)?;
+/// #     drop(listener);
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// Setting a local address for an outgoing connection.
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let remote = listener.local_addr()?;
+/// use mio::net::TcpSocket;
+///
+/// let socket = TcpSocket::new_v4()?;
+/// socket.bind("127.0.0.1:0".parse()?)?;
+///
+/// let stream = socket.connect(remote)?;
+/// #     drop(stream);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TcpSocket {
+    sys: sys::tcp::TcpSocket,
+}
+
+impl TcpSocket {
+    /// Create a new IPv4 TCP socket.
+    ///
+    /// This calls `socket(2)`.
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        sys::tcp::new_v4_socket().map(|sys| TcpSocket { sys })
+    }
+
+    /// Create a new IPv6 TCP socket.
+    ///
+    /// This calls `socket(2)`.
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        sys::tcp::new_v6_socket().map(|sys| TcpSocket { sys })
+    }
+
+    /// Create a new TCP socket in the address family of `addr`.
+    pub(crate) fn new_for_addr(addr: SocketAddr) -> io::Result<TcpSocket> {
+        sys::tcp::new_for_addr(addr).map(|sys| TcpSocket { sys })
+    }
+
+    /// Bind `addr` to the TCP socket.
+    ///
+    /// This is required before calling [`listen`], and can be used to pick
+    /// the local address of an outgoing connection before calling
+    /// [`connect`].
+    ///
+    /// [`listen`]: #method.listen
+    /// [`connect`]: #method.connect
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        sys::tcp::bind(self.sys, addr)
+    }
+
+    /// Connect the socket to `addr`.
+    ///
+    /// This consumes the socket and performs a non-blocking connect. The
+    /// returned `TcpStream` should be registered for writable events to wait
+    /// for the connection to be established, see [`TcpStream::connect`].
+    ///
+    /// [`TcpStream::connect`]: struct.TcpStream.html#method.connect
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        let stream = sys::tcp::connect(self.sys, addr)?;
+
+        // Don't close the socket, the stream owns it now.
+        mem::forget(self);
+        Ok(TcpStream::from_std(stream))
+    }
+
+    /// Connect the socket to `addr` using TCP Fast Open, sending `data` along
+    /// with the SYN.
+    ///
+    /// This consumes the socket and performs a non-blocking connect, like
+    /// [`connect`]. On success it returns the stream and the number of bytes
+    /// of `data` sent. The remaining bytes must be written once the stream
+    /// is connected, as usual.
+    ///
+    /// Data can only be sent with the SYN if the client has a Fast Open
+    /// cookie for `addr`, which it requests from the server during an earlier
+    /// connection. So the first connection to a server never sends any data,
+    /// and neither does a connection to a server that doesn't support Fast
+    /// Open. If Fast Open is disabled in the kernel this falls back to a
+    /// regular connect.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`connect`]: #method.connect
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn connect_with_data(
+        self,
+        addr: SocketAddr,
+        data: &[u8],
+    ) -> io::Result<(TcpStream, usize)> {
+        let (stream, n) = sys::tcp::connect_with_data(self.sys, addr, data)?;
+
+        // Don't close the socket, the stream owns it now.
+        mem::forget(self);
+        Ok((TcpStream::from_std(stream), n))
+    }
+
+    /// Listen for inbound connections, converting the socket to a
+    /// `TcpListener`.
+    ///
+    /// `backlog` is passed to `listen(2)` as the maximum length of the queue
+    /// of pending connections. The operating system may silently cap it.
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let listener = sys::tcp::listen(self.sys, backlog)?;
+
+        // Don't close the socket, the listener owns it now.
+        mem::forget(self);
+        Ok(TcpListener::from_std(listener))
+    }
+
+    /// Sets the value of `SO_REUSEADDR` on this socket.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        sys::tcp::set_reuseaddr(self.sys, reuseaddr)
+    }
+
+    /// Get the value of `SO_REUSEADDR` set on this socket.
+    pub fn get_reuseaddr(&self) -> io::Result<bool> {
+        sys::tcp::get_reuseaddr(self.sys)
+    }
+
+    /// Sets the value of `SO_REUSEPORT` on this socket.
+    ///
+    /// Only supported on Unix platforms that provide `SO_REUSEPORT`.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        sys::tcp::set_reuseport(self.sys, reuseport)
+    }
+
+    /// Get the value of `SO_REUSEPORT` set on this socket.
+    ///
+    /// Only supported on Unix platforms that provide `SO_REUSEPORT`.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn get_reuseport(&self) -> io::Result<bool> {
+        sys::tcp::get_reuseport(self.sys)
+    }
+
+    /// Sets the value of `SO_SNDBUF` on this socket.
+    ///
+    /// The operating system may adjust the value, use [`get_send_buffer_size`]
+    /// to retrieve the actual size.
+    ///
+    /// [`get_send_buffer_size`]: #method.get_send_buffer_size
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_send_buffer_size(self.sys, size)
+    }
+
+    /// Get the value of `SO_SNDBUF` set on this socket.
+    pub fn get_send_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_send_buffer_size(self.sys)
+    }
+
+    /// Sets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// The operating system may adjust the value, use [`get_recv_buffer_size`]
+    /// to retrieve the actual size.
+    ///
+    /// [`get_recv_buffer_size`]: #method.get_recv_buffer_size
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_recv_buffer_size(self.sys, size)
+    }
+
+    /// Get the value of `SO_RCVBUF` set on this socket.
+    pub fn get_recv_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_recv_buffer_size(self.sys)
+    }
+
+    /// Returns the local address of this socket.
+    ///
+    /// Returns an error if the socket is not bound.
+    pub fn get_localaddr(&self) -> io::Result<SocketAddr> {
+        sys::tcp::get_localaddr(self.sys)
+    }
+}
+
+impl Drop for TcpSocket {
+    fn drop(&mut self) {
+        sys::tcp::close(self.sys);
+    }
+}
+
+#[cfg(unix)]
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.sys;
+        mem::forget(self);
+        fd
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.sys
+    }
+}
+
+#[cfg(unix)]
+impl FromRawFd for TcpSocket {
+    /// Converts a `RawFd` to a `TcpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> TcpSocket {
+        TcpSocket { sys: fd }
+    }
+}
+
+#[cfg(windows)]
+impl IntoRawSocket for TcpSocket {
+    fn into_raw_socket(self) -> RawSocket {
+        let socket = self.sys as RawSocket;
+        mem::forget(self);
+        socket
+    }
+}
+
+#[cfg(windows)]
+impl AsRawSocket for TcpSocket {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.sys as RawSocket
+    }
+}
+
+#[cfg(windows)]
+impl FromRawSocket for TcpSocket {
+    /// Converts a `RawSocket` to a `TcpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_socket(socket: RawSocket) -> TcpSocket {
+        TcpSocket {
+            sys: socket as sys::tcp::TcpSocket,
+        }
+    }
+}
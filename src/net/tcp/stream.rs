@@ -5,8 +5,15 @@ use std::net::{self, Shutdown, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+use std::time::Duration;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::SystemTime;
 
 use crate::io_source::IoSource;
+use crate::net::TcpSocket;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::TxTimestamp;
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A non-blocking TCP stream between a local socket and a remote socket.
@@ -44,11 +51,72 @@ pub struct TcpStream {
     inner: IoSource<net::TcpStream>,
 }
 
+/// The state of a non-blocking connect, returned by
+/// [`TcpStream::connect_status`].
+///
+/// [`TcpStream::connect_status`]: struct.TcpStream.html#method.connect_status
+#[derive(Debug)]
+pub enum ConnectStatus {
+    /// The connection is established.
+    Connected,
+    /// The connection is still being established.
+    Pending,
+    /// The connection failed with the contained error.
+    Failed(io::Error),
+}
+
 impl TcpStream {
     /// Create a new TCP stream and issue a non-blocking connect to the
     /// specified address.
+    ///
+    /// Use [`TcpSocket`] to configure the socket before connecting, for
+    /// example to bind it to a specific local address.
+    ///
+    /// The connection is usually not established yet when this returns.
+    /// Register the stream for writable events and use [`connect_status`] to
+    /// find out whether the connection succeeded.
+    ///
+    /// [`TcpSocket`]: struct.TcpSocket.html
+    /// [`connect_status`]: #method.connect_status
     pub fn connect(addr: SocketAddr) -> io::Result<TcpStream> {
-        sys::tcp::connect(addr).map(TcpStream::from_std)
+        TcpSocket::new_for_addr(addr)?.connect(addr)
+    }
+
+    /// Create a new TCP stream and issue a non-blocking connect to the
+    /// specified address using TCP Fast Open, sending `data` along with the
+    /// SYN.
+    ///
+    /// Returns the stream and the number of bytes of `data` sent, the rest
+    /// must be written once the stream is connected. See
+    /// [`TcpSocket::connect_with_data`] for when data is sent.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`TcpSocket::connect_with_data`]: struct.TcpSocket.html#method.connect_with_data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::net::{TcpListener, SocketAddr};
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let address: SocketAddr = "127.0.0.1:0".parse()?;
+    /// let listener = TcpListener::bind(address)?;
+    /// use mio::net::TcpStream;
+    ///
+    /// let request = b"GET / HTTP/1.0\r\n\r\n";
+    /// let (stream, n) = TcpStream::connect_with_data(listener.local_addr()?, request)?;
+    ///
+    /// // Once the stream is connected, write the part of the request that
+    /// // wasn't sent with the SYN, `&request[n..]`.
+    /// #     drop((stream, n));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn connect_with_data(addr: SocketAddr, data: &[u8]) -> io::Result<(TcpStream, usize)> {
+        TcpSocket::new_for_addr(addr)?.connect_with_data(addr, data)
     }
 
     /// Creates a new `TcpStream` from a standard `net::TcpStream`.
@@ -69,6 +137,73 @@ impl TcpStream {
         }
     }
 
+    /// Returns the state of the connect issued by [`connect`].
+    ///
+    /// Call this after receiving a writable (or error) event for the stream.
+    /// It returns:
+    ///
+    /// * [`Connected`] once the connection is established, even if it has
+    ///   since been closed by the peer.
+    /// * [`Pending`] while the connection is still being established, for
+    ///   example after a spurious event. Wait for the next event and check
+    ///   again.
+    /// * [`Failed`] if the connection failed, for example because it was
+    ///   refused or timed out, or if it was reset before this was called. The
+    ///   error is taken from the socket (like [`take_error`]), so calling
+    ///   this again returns a less specific error.
+    ///
+    /// On platforms using epoll or kqueue a failed connect is never reported
+    /// as pending, even if its error was already taken by another call.
+    ///
+    /// On Windows a failed connect is only reported as [`Failed`] while its
+    /// error hasn't been taken. Once it is, by calling this or [`take_error`],
+    /// the connect can't be told apart from one that is in progress and is
+    /// reported as [`Pending`] from then on. Treat the first [`Failed`] as
+    /// final and don't call [`take_error`] before this.
+    ///
+    /// [`connect`]: #method.connect
+    /// [`Connected`]: enum.ConnectStatus.html#variant.Connected
+    /// [`Pending`]: enum.ConnectStatus.html#variant.Pending
+    /// [`Failed`]: enum.ConnectStatus.html#variant.Failed
+    /// [`take_error`]: #method.take_error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::net::{TcpListener, SocketAddr};
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let address: SocketAddr = "127.0.0.1:0".parse()?;
+    /// let listener = TcpListener::bind(address)?;
+    /// use mio::net::{ConnectStatus, TcpStream};
+    /// use mio::{Events, Interest, Poll, Token};
+    ///
+    /// let mut stream = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    /// poll.registry().register(&mut stream, Token(0), Interest::WRITABLE)?;
+    ///
+    /// loop {
+    ///     poll.poll(&mut events, None)?;
+    ///     match stream.connect_status() {
+    ///         ConnectStatus::Connected => break,
+    ///         ConnectStatus::Pending => continue,
+    ///         ConnectStatus::Failed(err) => return Err(err.into()),
+    ///     }
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn connect_status(&self) -> ConnectStatus {
+        match sys::tcp::connect_status(&self.inner) {
+            Ok(true) => ConnectStatus::Connected,
+            Ok(false) => ConnectStatus::Pending,
+            Err(err) => ConnectStatus::Failed(err),
+        }
+    }
+
     /// Returns the socket address of the remote peer of this TCP connection.
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         self.inner.peer_addr()
@@ -149,6 +284,220 @@ impl TcpStream {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If enabled, the operating system periodically sends keepalive probes
+    /// on an idle connection. If the peer doesn't respond to the probes the
+    /// connection is closed. The socket then reports an error event, and
+    /// reading or writing returns an error (usually `TimedOut`).
+    ///
+    /// See [`set_keepalive_time`], [`set_keepalive_interval`] and
+    /// [`set_keepalive_retries`] to configure the probes.
+    ///
+    /// [`set_keepalive_time`]: #method.set_keepalive_time
+    /// [`set_keepalive_interval`]: #method.set_keepalive_interval
+    /// [`set_keepalive_retries`]: #method.set_keepalive_retries
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        sys::tcp::set_keepalive(self.as_socket(), keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`set_keepalive`][link].
+    ///
+    /// [link]: #method.set_keepalive
+    pub fn keepalive(&self) -> io::Result<bool> {
+        sys::tcp::get_keepalive(self.as_socket())
+    }
+
+    /// Sets the amount of time the connection must be idle before the first
+    /// keepalive probe is sent.
+    ///
+    /// This sets the `TCP_KEEPIDLE` option (`TCP_KEEPALIVE` on macOS and iOS).
+    /// The time is rounded down to whole seconds.
+    ///
+    /// # Notes
+    ///
+    /// Not available on OpenBSD, Solaris and illumos. On Windows this
+    /// requires Windows 10, version 1709 or later.
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        sys::tcp::set_keepalive_time(self.as_socket(), time)
+    }
+
+    /// Gets the amount of time the connection must be idle before the first
+    /// keepalive probe is sent.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_time`][link].
+    ///
+    /// [link]: #method.set_keepalive_time
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        sys::tcp::get_keepalive_time(self.as_socket())
+    }
+
+    /// Sets the time between keepalive probes, if the previous probe wasn't
+    /// acknowledged.
+    ///
+    /// This sets the `TCP_KEEPINTVL` option. The interval is rounded down to
+    /// whole seconds.
+    ///
+    /// # Notes
+    ///
+    /// Not available on OpenBSD, Solaris and illumos. On Windows this
+    /// requires Windows 10, version 1709 or later.
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        sys::tcp::set_keepalive_interval(self.as_socket(), interval)
+    }
+
+    /// Gets the time between keepalive probes.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_interval`][link].
+    ///
+    /// [link]: #method.set_keepalive_interval
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        sys::tcp::get_keepalive_interval(self.as_socket())
+    }
+
+    /// Sets the number of unacknowledged keepalive probes sent before the
+    /// connection is considered dead.
+    ///
+    /// This sets the `TCP_KEEPCNT` option.
+    ///
+    /// # Notes
+    ///
+    /// Not available on OpenBSD, Solaris and illumos. On Windows this
+    /// requires Windows 10, version 1709 or later.
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        sys::tcp::set_keepalive_retries(self.as_socket(), retries)
+    }
+
+    /// Gets the number of unacknowledged keepalive probes sent before the
+    /// connection is considered dead.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_retries`][link].
+    ///
+    /// [link]: #method.set_keepalive_retries
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        sys::tcp::get_keepalive_retries(self.as_socket())
+    }
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time transmitted data may remain
+    /// unacknowledged before the connection is closed, which also limits the
+    /// time spent on keepalive probes. `None` restores the system default.
+    /// The timeout is rounded down to whole milliseconds.
+    ///
+    /// Only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        sys::tcp::set_user_timeout(self.as_socket(), timeout)
+    }
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_user_timeout`][link].
+    ///
+    /// [link]: #method.set_user_timeout
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        sys::tcp::get_user_timeout(self.as_socket())
+    }
+
+    /// Sets the value of the `SO_TIMESTAMPNS` option on this socket.
+    ///
+    /// When enabled, [`recv_timestamped`] reports the time the kernel
+    /// received the data.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`recv_timestamped`]: #method.recv_timestamped
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_timestamps(&self, on: bool) -> io::Result<()> {
+        sys::timestamp::set_recv_timestamps(self.as_raw_fd(), on)
+    }
+
+    /// Gets the value of the `SO_TIMESTAMPNS` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_timestamps`][link].
+    ///
+    /// [link]: #method.set_recv_timestamps
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_timestamps(&self) -> io::Result<bool> {
+        sys::timestamp::recv_timestamps(self.as_raw_fd())
+    }
+
+    /// Receives data on the socket, along with the time the kernel received
+    /// it.
+    ///
+    /// On success, returns the number of bytes read and the receive time of
+    /// the most recent segment read. The time is `None` unless enabled using
+    /// [`set_recv_timestamps`]. It may also be `None` for data received
+    /// shortly after enabling timestamps while no other socket on the system
+    /// uses them, as the kernel turns timestamping on asynchronously.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`set_recv_timestamps`]: #method.set_recv_timestamps
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_timestamped(&self, buf: &mut [u8]) -> io::Result<(usize, Option<SystemTime>)> {
+        self.inner
+            .do_io(|inner| sys::timestamp::recv_timestamped(inner.as_raw_fd(), buf))
+            .map(|(n, _, time)| (n, time))
+    }
+
+    /// Enables or disables software transmit timestamps on this socket, using
+    /// the `SO_TIMESTAMPING` option.
+    ///
+    /// When enabled, the kernel queues a timestamp on the error queue of the
+    /// socket when data is handed to the network device. The socket then
+    /// reports an error event (see [`Event::is_error`]) and the timestamps
+    /// can be read using [`recv_tx_timestamp`].
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`Event::is_error`]: ../event/struct.Event.html#method.is_error
+    /// [`recv_tx_timestamp`]: #method.recv_tx_timestamp
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_tx_timestamps(&self, on: bool) -> io::Result<()> {
+        sys::timestamp::set_tx_timestamps(self.as_raw_fd(), on)
+    }
+
+    /// Returns whether software transmit timestamps are enabled on this
+    /// socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_tx_timestamps`][link].
+    ///
+    /// [link]: #method.set_tx_timestamps
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn tx_timestamps(&self) -> io::Result<bool> {
+        sys::timestamp::tx_timestamps(self.as_raw_fd())
+    }
+
+    /// Reads a transmit timestamp from the error queue of the socket.
+    ///
+    /// Returns a `WouldBlock` error if no timestamp is queued. Returns an
+    /// `InvalidData` error, after removing it from the queue, if the next
+    /// message on the error queue is not a transmit timestamp.
+    ///
+    /// Only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_tx_timestamp(&self) -> io::Result<TxTimestamp> {
+        self.inner
+            .do_io(|inner| sys::timestamp::recv_tx_timestamp(inner.as_raw_fd()))
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -167,6 +516,16 @@ impl TcpStream {
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.peek(buf)
     }
+
+    #[cfg(unix)]
+    fn as_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_fd()
+    }
+
+    #[cfg(windows)]
+    fn as_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_socket() as sys::tcp::TcpSocket
+    }
 }
 
 impl Read for TcpStream {
@@ -0,0 +1,38 @@
+use std::time::SystemTime;
+
+/// A transmit timestamp read from the error queue of a socket.
+///
+/// See [`UdpSocket::recv_tx_timestamp`] and
+/// [`TcpStream::recv_tx_timestamp`].
+///
+/// Only available on Linux and Android.
+///
+/// [`UdpSocket::recv_tx_timestamp`]: struct.UdpSocket.html#method.recv_tx_timestamp
+/// [`TcpStream::recv_tx_timestamp`]: struct.TcpStream.html#method.recv_tx_timestamp
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TxTimestamp {
+    time: SystemTime,
+    id: u32,
+}
+
+impl TxTimestamp {
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn new(time: SystemTime, id: u32) -> TxTimestamp {
+        TxTimestamp { time, id }
+    }
+
+    /// The time the kernel handed the data to the network device.
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Identifies the data the timestamp belongs to.
+    ///
+    /// For UDP sockets this counts the datagrams sent since transmit
+    /// timestamps were enabled, starting at 0. For TCP streams it is the
+    /// offset of the last byte of the send call, counting the bytes sent
+    /// since transmit timestamps were enabled.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
@@ -8,16 +8,26 @@
 //! [portability guidelines]: ../struct.Poll.html#portability
 
 use crate::io_source::IoSource;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::TxTimestamp;
+#[cfg(unix)]
+use crate::net::{RecvMsg, SendMsg};
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::fmt;
 use std::io;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::net::IpAddr;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::SystemTime;
 
 /// A User Datagram Protocol socket.
 ///
@@ -255,6 +265,60 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.peek_from(buf))
     }
 
+    /// Like [`send_to`], except that it sends data from a slice of buffers.
+    ///
+    /// The buffers are sent as a single datagram, using `sendmsg(2)`. On
+    /// success, returns the number of bytes written.
+    ///
+    /// Only available on Unix.
+    ///
+    /// [`send_to`]: #method.send_to
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io::IoSlice;
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // Send a header and a payload, without copying them into one buffer.
+    /// let header = [1, 2];
+    /// let payload = b"hello";
+    /// let bufs = [IoSlice::new(&header), IoSlice::new(payload)];
+    /// let bytes_sent = socket.send_to_vectored(&bufs, "127.0.0.1:11100".parse()?)?;
+    /// assert_eq!(bytes_sent, 7);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn send_to_vectored(&self, bufs: &[IoSlice<'_>], target: SocketAddr) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_vectored(inner, bufs, Some(&target)))
+    }
+
+    /// Like [`recv_from`], except that it reads into a slice of buffers.
+    ///
+    /// The datagram is scattered over the buffers in order, using
+    /// `recvmsg(2)`. On success, returns the number of bytes read and the
+    /// address from whence the data came. If the datagram is larger than the
+    /// buffers the excess data is lost.
+    ///
+    /// Only available on Unix.
+    ///
+    /// [`recv_from`]: #method.recv_from
+    #[cfg(unix)]
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_vectored(inner, bufs))
+    }
+
     /// Sends data on the socket to the address previously bound via connect(). On success,
     /// returns the number of bytes written.
     pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
@@ -289,6 +353,463 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.peek(buf))
     }
 
+    /// Like [`send`], except that it sends data from a slice of buffers.
+    ///
+    /// The buffers are sent as a single datagram to the address previously
+    /// bound via connect(), using `sendmsg(2)`. On success, returns the number
+    /// of bytes written.
+    ///
+    /// Only available on Unix.
+    ///
+    /// [`send`]: #method.send
+    #[cfg(unix)]
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_vectored(inner, bufs, None))
+    }
+
+    /// Like [`recv`], except that it reads into a slice of buffers.
+    ///
+    /// Receives a datagram from the address to which the socket is connected,
+    /// scattering it over the buffers in order. On success, returns the
+    /// number of bytes read. If the datagram is larger than the buffers the
+    /// excess data is lost.
+    ///
+    /// Only available on Unix.
+    ///
+    /// [`recv`]: #method.recv
+    #[cfg(unix)]
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_vectored_connected(inner, bufs))
+    }
+
+    /// Sends a batch of datagrams, each to its own address or to the address
+    /// previously bound via connect().
+    ///
+    /// On Linux and Android this uses a single `sendmmsg(2)` call, on other
+    /// platforms the datagrams are sent one at a time. On success, returns
+    /// the number of datagrams sent, which may be less than `msgs.len()`. An
+    /// error is only returned if the first datagram couldn't be sent.
+    ///
+    /// Only available on Unix.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{SendMsg, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// let addr1 = "127.0.0.1:11100".parse()?;
+    /// let addr2 = "127.0.0.1:11101".parse()?;
+    /// let msgs = [SendMsg::to(b"hello", addr1), SendMsg::to(b"world", addr2)];
+    /// let sent = socket.send_batch(&msgs)?;
+    /// println!("sent {} datagrams", sent);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn send_batch(&self, msgs: &[SendMsg<'_, SocketAddr>]) -> io::Result<usize> {
+        self.inner.do_io(|inner| sys::udp::send_batch(inner, msgs))
+    }
+
+    /// Receives a batch of datagrams, each into its own buffer.
+    ///
+    /// On Linux and Android this uses a single `recvmmsg(2)` call, on other
+    /// platforms the datagrams are received one at a time. On success,
+    /// returns the number of datagrams received, which may be less than
+    /// `msgs.len()`; only those messages are updated. Returns a
+    /// `WouldBlock` error if no datagram is available.
+    ///
+    /// Only available on Unix.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{RecvMsg, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // We must check if the socket is readable before calling recv_batch,
+    /// // or we could run into a WouldBlock error.
+    ///
+    /// let mut bufs = [[0; 1500]; 8];
+    /// let mut msgs: Vec<_> = bufs.iter_mut().map(|buf| RecvMsg::new(&mut buf[..])).collect();
+    /// let n = socket.recv_batch(&mut msgs)?;
+    /// for msg in &msgs[..n] {
+    ///     println!("received {:?} from {:?}", msg.data(), msg.addr());
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn recv_batch(&self, msgs: &mut [RecvMsg<'_, SocketAddr>]) -> io::Result<usize> {
+        self.inner.do_io(|inner| sys::udp::recv_batch(inner, msgs))
+    }
+
+    /// Sends `buf` as a train of datagrams of `segment_size` bytes each to
+    /// `target`, using UDP generic segmentation offload (`UDP_SEGMENT`).
+    ///
+    /// The kernel (or network card) splits `buf` into datagrams of
+    /// `segment_size` bytes; only the last one may be shorter. On success,
+    /// returns the number of bytes written. Use [`supports_gso`] to check if
+    /// the kernel supports this.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // Sends four datagrams of 1200 bytes each in a single call.
+    /// let buf = [0; 4800];
+    /// let bytes_sent = socket.send_to_segmented(&buf, 1200, "127.0.0.1:11100".parse()?)?;
+    /// assert_eq!(bytes_sent, 4800);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`supports_gso`]: #method.supports_gso
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_to_segmented(
+        &self,
+        buf: &[u8],
+        segment_size: u16,
+        target: SocketAddr,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_segmented(inner, buf, segment_size, Some(&target)))
+    }
+
+    /// Like [`send_to_segmented`], except that it sends to the address
+    /// previously bound via connect().
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`send_to_segmented`]: #method.send_to_segmented
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_segmented(&self, buf: &[u8], segment_size: u16) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_segmented(inner, buf, segment_size, None))
+    }
+
+    /// Sets the value of the `UDP_GRO` option for this socket.
+    ///
+    /// When enabled, the kernel may coalesce consecutive datagrams from the
+    /// same sender into a single buffer, which can be received using
+    /// [`recv_from_gro`]. Use [`supports_gro`] to check if the kernel
+    /// supports this.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`recv_from_gro`]: #method.recv_from_gro
+    /// [`supports_gro`]: #method.supports_gro
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_gro(&self, enable: bool) -> io::Result<()> {
+        sys::udp::set_gro(&self.inner, enable)
+    }
+
+    /// Receives a datagram, or several coalesced datagrams if `UDP_GRO` is
+    /// enabled, into `buf`.
+    ///
+    /// On success, returns the number of bytes read, the address from whence
+    /// the data came and the size of the coalesced segments. The segment
+    /// size is `None` if the kernel didn't coalesce any datagrams, in which
+    /// case `buf` holds a single datagram. Otherwise every segment in `buf`
+    /// is exactly that long, except possibly the last one.
+    ///
+    /// `buf` should be large enough to hold a coalesced buffer, up to 65535
+    /// bytes, or the excess data is lost.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// socket.set_gro(true)?;
+    ///
+    /// // We must check if the socket is readable before calling
+    /// // recv_from_gro, or we could run into a WouldBlock error.
+    ///
+    /// let mut buf = [0; 65535];
+    /// let (n, addr, segment_size) = socket.recv_from_gro(&mut buf)?;
+    /// let segment_size = segment_size.map_or(n, usize::from);
+    /// for datagram in buf[..n].chunks(segment_size) {
+    ///     println!("received {:?} from {:?}", datagram, addr);
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_gro(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, Option<u16>)> {
+        self.inner.do_io(|inner| sys::udp::recv_gro(inner, buf))
+    }
+
+    /// Returns `true` if the kernel supports UDP generic segmentation offload
+    /// (`UDP_SEGMENT`), as used by [`send_to_segmented`].
+    ///
+    /// This probes the kernel using a temporary socket, so callers should
+    /// cache the result.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`send_to_segmented`]: #method.send_to_segmented
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn supports_gso() -> bool {
+        sys::udp::probe_offload().0
+    }
+
+    /// Returns `true` if the kernel supports UDP generic receive offload
+    /// (`UDP_GRO`), as used by [`recv_from_gro`].
+    ///
+    /// This probes the kernel using a temporary socket, so callers should
+    /// cache the result.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`recv_from_gro`]: #method.recv_from_gro
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn supports_gro() -> bool {
+        sys::udp::probe_offload().1
+    }
+
+    /// Sets the value of the `IP_PKTINFO` option for this socket.
+    ///
+    /// When enabled, [`recv_msg`] reports the destination address and
+    /// interface of IPv4 datagrams.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`recv_msg`]: #method.recv_msg
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_pktinfo_v4(&self, on: bool) -> io::Result<()> {
+        sys::udp::set_pktinfo_v4(&self.inner, on)
+    }
+
+    /// Gets the value of the `IP_PKTINFO` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_pktinfo_v4`][link].
+    ///
+    /// [link]: #method.set_pktinfo_v4
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn pktinfo_v4(&self) -> io::Result<bool> {
+        sys::udp::pktinfo_v4(&self.inner)
+    }
+
+    /// Sets the value of the `IPV6_RECVPKTINFO` option for this socket.
+    ///
+    /// When enabled, [`recv_msg`] reports the destination address and
+    /// interface of IPv6 datagrams, including IPv4 datagrams received on a
+    /// dual-stack socket (as IPv4-mapped addresses).
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`recv_msg`]: #method.recv_msg
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_pktinfo_v6(&self, on: bool) -> io::Result<()> {
+        sys::udp::set_recv_pktinfo_v6(&self.inner, on)
+    }
+
+    /// Gets the value of the `IPV6_RECVPKTINFO` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_pktinfo_v6`][link].
+    ///
+    /// [link]: #method.set_recv_pktinfo_v6
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_pktinfo_v6(&self) -> io::Result<bool> {
+        sys::udp::recv_pktinfo_v6(&self.inner)
+    }
+
+    /// Receives a datagram into `buf`, along with the packet info of the
+    /// datagram.
+    ///
+    /// On success, returns the number of bytes read, the address from whence
+    /// the data came and the destination address and interface the datagram
+    /// was received on. The packet info is `None` unless enabled using
+    /// [`set_pktinfo_v4`] or [`set_recv_pktinfo_v6`].
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:11100".parse()?)?;
+    /// socket.set_pktinfo_v4(true)?;
+    ///
+    /// // We must check if the socket is readable before calling recv_msg,
+    /// // or we could run into a WouldBlock error.
+    ///
+    /// let mut buf = [0; 1500];
+    /// let (n, addr, info) = socket.recv_msg(&mut buf)?;
+    /// // Reply from the address the client sent the request to.
+    /// if let Some(info) = info {
+    ///     socket.send_msg(&buf[..n], addr, &info)?;
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`set_pktinfo_v4`]: #method.set_pktinfo_v4
+    /// [`set_recv_pktinfo_v6`]: #method.set_recv_pktinfo_v6
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_msg(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, Option<PacketInfo>)> {
+        self.inner.do_io(|inner| sys::udp::recv_pktinfo(inner, buf))
+    }
+
+    /// Sends data on the socket to `target`, using the source address and
+    /// outgoing interface in `info`.
+    ///
+    /// An unspecified address in `info` lets the kernel pick the source
+    /// address, and an interface index of 0 lets it pick the interface. The
+    /// address in `info` must be of the same family as the socket; use an
+    /// IPv4-mapped IPv6 address on a dual-stack socket. On success, returns
+    /// the number of bytes written.
+    ///
+    /// Only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_msg(&self, buf: &[u8], target: SocketAddr, info: &PacketInfo) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_pktinfo(inner, buf, &target, info))
+    }
+
+    /// Sets the value of the `SO_TIMESTAMPNS` option for this socket.
+    ///
+    /// When enabled, [`recv_from_timestamped`] reports the time the kernel
+    /// received each datagram.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`recv_from_timestamped`]: #method.recv_from_timestamped
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_timestamps(&self, on: bool) -> io::Result<()> {
+        sys::timestamp::set_recv_timestamps(self.as_raw_fd(), on)
+    }
+
+    /// Gets the value of the `SO_TIMESTAMPNS` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_timestamps`][link].
+    ///
+    /// [link]: #method.set_recv_timestamps
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_timestamps(&self) -> io::Result<bool> {
+        sys::timestamp::recv_timestamps(self.as_raw_fd())
+    }
+
+    /// Receives a datagram into `buf`, along with the time the kernel
+    /// received it.
+    ///
+    /// On success, returns the number of bytes read, the address from whence
+    /// the data came and the receive time. The time is `None` unless enabled
+    /// using [`set_recv_timestamps`]. It may also be `None` for datagrams
+    /// received shortly after enabling timestamps while no other socket on the
+    /// system uses them, as the kernel turns timestamping on asynchronously.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    /// use std::time::SystemTime;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// socket.set_recv_timestamps(true)?;
+    ///
+    /// // We must check if the socket is readable before calling
+    /// // recv_from_timestamped, or we could run into a WouldBlock error.
+    ///
+    /// let mut buf = [0; 1500];
+    /// let (n, addr, time) = socket.recv_from_timestamped(&mut buf)?;
+    /// if let Some(time) = time {
+    ///     let delay = SystemTime::now().duration_since(time)?;
+    ///     println!("received {} bytes from {}, {:?} ago", n, addr, delay);
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`set_recv_timestamps`]: #method.set_recv_timestamps
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_timestamped(
+        &self,
+        buf: &mut [u8],
+    ) -> io::Result<(usize, SocketAddr, Option<SystemTime>)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_timestamped(inner, buf))
+    }
+
+    /// Enables or disables software transmit timestamps for this socket,
+    /// using the `SO_TIMESTAMPING` option.
+    ///
+    /// When enabled, the kernel queues a timestamp on the error queue of the
+    /// socket when a datagram is handed to the network device. The socket
+    /// then reports an error event (see [`Event::is_error`]) and the
+    /// timestamps can be read using [`recv_tx_timestamp`].
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`Event::is_error`]: ../event/struct.Event.html#method.is_error
+    /// [`recv_tx_timestamp`]: #method.recv_tx_timestamp
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_tx_timestamps(&self, on: bool) -> io::Result<()> {
+        sys::timestamp::set_tx_timestamps(self.as_raw_fd(), on)
+    }
+
+    /// Returns whether software transmit timestamps are enabled for this
+    /// socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_tx_timestamps`][link].
+    ///
+    /// [link]: #method.set_tx_timestamps
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn tx_timestamps(&self) -> io::Result<bool> {
+        sys::timestamp::tx_timestamps(self.as_raw_fd())
+    }
+
+    /// Reads a transmit timestamp from the error queue of the socket.
+    ///
+    /// Returns a `WouldBlock` error if no timestamp is queued. Returns an
+    /// `InvalidData` error, after removing it from the queue, if the next
+    /// message on the error queue is not a transmit timestamp.
+    ///
+    /// Only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_tx_timestamp(&self) -> io::Result<TxTimestamp> {
+        self.inner
+            .do_io(|inner| sys::timestamp::recv_tx_timestamp(inner.as_raw_fd()))
+    }
+
     /// Connects the UDP socket setting the default destination for `send()`
     /// and limiting packets that are read via `recv` from the address specified
     /// in `addr`.
@@ -512,6 +1033,43 @@ impl UdpSocket {
     }
 }
 
+/// The destination address and interface of a received datagram, or the
+/// source address and outgoing interface of a datagram to send.
+///
+/// See [`UdpSocket::recv_msg`] and [`UdpSocket::send_msg`].
+///
+/// Only available on Linux and Android.
+///
+/// [`UdpSocket::recv_msg`]: struct.UdpSocket.html#method.recv_msg
+/// [`UdpSocket::send_msg`]: struct.UdpSocket.html#method.send_msg
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PacketInfo {
+    addr: IpAddr,
+    interface: u32,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl PacketInfo {
+    /// Create packet info with address `addr` and interface index
+    /// `interface`.
+    pub fn new(addr: IpAddr, interface: u32) -> PacketInfo {
+        PacketInfo { addr, interface }
+    }
+
+    /// The destination address of a received datagram, or the source address
+    /// of a datagram to send.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// The index of the interface a datagram was received on, or should be
+    /// sent from.
+    pub fn interface(&self) -> u32 {
+        self.interface
+    }
+}
+
 impl event::Source for UdpSocket {
     fn register(
         &mut self,
@@ -1,4 +1,7 @@
 use crate::io_source::IoSource;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::UCred;
+use crate::net::{ReceivedFds, RecvMsg, SendMsg};
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::net::Shutdown;
@@ -18,6 +21,17 @@ impl UnixDatagram {
         sys::uds::datagram::bind(path.as_ref()).map(UnixDatagram::from_std)
     }
 
+    /// Creates a Unix datagram socket bound to the given socket address.
+    ///
+    /// Unlike [`bind`] this can bind to an abstract address, see
+    /// [`SocketAddr::from_abstract_name`].
+    ///
+    /// [`bind`]: #method.bind
+    /// [`SocketAddr::from_abstract_name`]: struct.SocketAddr.html#method.from_abstract_name
+    pub fn bind_addr(address: &sys::SocketAddr) -> io::Result<UnixDatagram> {
+        sys::uds::datagram::bind_addr(address).map(UnixDatagram::from_std)
+    }
+
     /// Creates a new `UnixDatagram` from a standard `net::UnixDatagram`.
     ///
     /// This function is intended to be used to wrap a Unix datagram from the
@@ -35,6 +49,11 @@ impl UnixDatagram {
         self.inner.connect(path)
     }
 
+    /// Connects the socket to the specified socket address.
+    pub fn connect_addr(&self, address: &sys::SocketAddr) -> io::Result<()> {
+        sys::uds::datagram::connect_addr(&self.inner, address)
+    }
+
     /// Creates a Unix Datagram socket which is not bound to any address.
     pub fn unbound() -> io::Result<UnixDatagram> {
         sys::uds::datagram::unbound().map(UnixDatagram::from_std)
@@ -85,6 +104,14 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.send_to(buf, path))
     }
 
+    /// Sends data on the socket to the specified socket address.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to_addr(&self, buf: &[u8], address: &sys::SocketAddr) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_to_addr(inner, buf, address))
+    }
+
     /// Sends data on the socket to the socket's peer.
     ///
     /// The peer address may be set by the `connect` method, and this method
@@ -95,6 +122,106 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.send(buf))
     }
 
+    /// Sends a batch of datagrams, each to its own address or to the
+    /// socket's peer.
+    ///
+    /// On Linux and Android this uses a single `sendmmsg(2)` call, on other
+    /// platforms the datagrams are sent one at a time. On success, returns
+    /// the number of datagrams sent, which may be less than `msgs.len()`. An
+    /// error is only returned if the first datagram couldn't be sent.
+    pub fn send_batch(&self, msgs: &[SendMsg<'_, &Path>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_batch(inner, msgs))
+    }
+
+    /// Receives a batch of datagrams, each into its own buffer.
+    ///
+    /// On Linux and Android this uses a single `recvmmsg(2)` call, on other
+    /// platforms the datagrams are received one at a time. On success,
+    /// returns the number of datagrams received, which may be less than
+    /// `msgs.len()`; only those messages are updated. Returns a
+    /// `WouldBlock` error if no datagram is available.
+    pub fn recv_batch(&self, msgs: &mut [RecvMsg<'_, sys::SocketAddr>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_batch(inner, msgs))
+    }
+
+    /// Sends data on the socket to the socket's peer along with the file
+    /// descriptors in `fds`, using `SCM_RIGHTS`.
+    ///
+    /// The file descriptors are duplicated into the receiving process; they
+    /// remain open in this process. On success, returns the number of bytes
+    /// written.
+    pub fn send_with_fds(&self, buf: &[u8], fds: &[RawFd]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_with_fds(inner, buf, fds))
+    }
+
+    /// Receives a datagram along with at most `max_fds` file descriptors
+    /// passed using `SCM_RIGHTS`.
+    ///
+    /// On success, returns the number of bytes read and the file descriptors
+    /// received, which are close-on-exec and closed when dropped. If the
+    /// sender passed more than `max_fds` file descriptors
+    /// [`ReceivedFds::is_truncated`] returns `true`. On Linux and Android the
+    /// excess file descriptors are closed, on other platforms they may be
+    /// leaked, so `max_fds` should be large enough for what the sender
+    /// passes.
+    ///
+    /// [`ReceivedFds::is_truncated`]: struct.ReceivedFds.html#method.is_truncated
+    pub fn recv_with_fds(
+        &self,
+        buf: &mut [u8],
+        max_fds: usize,
+    ) -> io::Result<(usize, ReceivedFds)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_with_fds(inner, buf, max_fds))
+    }
+
+    /// Sets the value of the `SO_PASSCRED` option.
+    ///
+    /// When enabled, the kernel attaches the credentials of the sending
+    /// process to every datagram received, which can be read using
+    /// [`recv_from_with_cred`].
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`recv_from_with_cred`]: #method.recv_from_with_cred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_passcred(&self, on: bool) -> io::Result<()> {
+        sys::uds::datagram::set_passcred(&self.inner, on)
+    }
+
+    /// Gets the value of the `SO_PASSCRED` option.
+    ///
+    /// For more information about this option, see
+    /// [`set_passcred`][link].
+    ///
+    /// [link]: #method.set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn passcred(&self) -> io::Result<bool> {
+        sys::uds::datagram::passcred(&self.inner)
+    }
+
+    /// Receives data from the socket, along with the credentials of the
+    /// sending process (`SCM_CREDENTIALS`).
+    ///
+    /// On success, returns the number of bytes read, the address from whence
+    /// the data came and the credentials of the sender. The credentials are
+    /// `None` unless enabled using [`set_passcred`].
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// [`set_passcred`]: #method.set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_with_cred(
+        &self,
+        buf: &mut [u8],
+    ) -> io::Result<(usize, sys::SocketAddr, Option<UCred>)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_from_with_cred(inner, buf))
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
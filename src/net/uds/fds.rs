@@ -0,0 +1,109 @@
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::{fmt, slice, vec};
+
+/// An owned file descriptor received over a Unix socket.
+///
+/// The file descriptor has the close-on-exec flag set and is closed when
+/// dropped. Use [`IntoRawFd`] to convert it into, for example, a
+/// `TcpListener`.
+///
+/// [`IntoRawFd`]: https://doc.rust-lang.org/std/os/unix/io/trait.IntoRawFd.html
+pub struct ReceivedFd {
+    fd: RawFd,
+}
+
+impl AsRawFd for ReceivedFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for ReceivedFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> ReceivedFd {
+        ReceivedFd { fd }
+    }
+}
+
+impl IntoRawFd for ReceivedFd {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.fd;
+        std::mem::forget(self);
+        fd
+    }
+}
+
+impl Drop for ReceivedFd {
+    fn drop(&mut self) {
+        let _ = unsafe { libc::close(self.fd) };
+    }
+}
+
+impl fmt::Debug for ReceivedFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ReceivedFd").field(&self.fd).finish()
+    }
+}
+
+/// The file descriptors received along with a message, see
+/// [`UnixStream::recv_with_fds`] and [`UnixDatagram::recv_with_fds`].
+///
+/// [`UnixStream::recv_with_fds`]: struct.UnixStream.html#method.recv_with_fds
+/// [`UnixDatagram::recv_with_fds`]: struct.UnixDatagram.html#method.recv_with_fds
+#[derive(Debug)]
+pub struct ReceivedFds {
+    fds: Vec<ReceivedFd>,
+    truncated: bool,
+}
+
+impl ReceivedFds {
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn new(fds: Vec<ReceivedFd>, truncated: bool) -> ReceivedFds {
+        ReceivedFds { fds, truncated }
+    }
+
+    /// Returns `true` if the control data was truncated (`MSG_CTRUNC`), i.e.
+    /// the sender passed more file descriptors than were asked for. On Linux
+    /// and Android the kernel closes the file descriptors that didn't fit, on
+    /// other platforms they may be leaked into the process.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// The number of file descriptors received.
+    pub fn len(&self) -> usize {
+        self.fds.len()
+    }
+
+    /// Returns `true` if no file descriptors were received.
+    pub fn is_empty(&self) -> bool {
+        self.fds.is_empty()
+    }
+
+    /// Iterate over the file descriptors received.
+    pub fn iter(&self) -> slice::Iter<'_, ReceivedFd> {
+        self.fds.iter()
+    }
+
+    /// Take ownership of the file descriptors received.
+    pub fn into_vec(self) -> Vec<ReceivedFd> {
+        self.fds
+    }
+}
+
+impl IntoIterator for ReceivedFds {
+    type Item = ReceivedFd;
+    type IntoIter = vec::IntoIter<ReceivedFd>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fds.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ReceivedFds {
+    type Item = &'a ReceivedFd;
+    type IntoIter = slice::Iter<'a, ReceivedFd>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fds.iter()
+    }
+}
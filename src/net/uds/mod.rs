@@ -1,10 +1,21 @@
 mod datagram;
 pub use self::datagram::UnixDatagram;
 
+mod fds;
+pub use self::fds::{ReceivedFd, ReceivedFds};
+
 mod listener;
 pub use self::listener::UnixListener;
 
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+mod seqpacket;
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+pub use self::seqpacket::{UnixSeqpacket, UnixSeqpacketListener};
+
 mod stream;
 pub use self::stream::UnixStream;
 
+mod ucred;
+pub use self::ucred::UCred;
+
 pub use crate::sys::SocketAddr;
@@ -0,0 +1,291 @@
+use crate::io_source::IoSource;
+use crate::net::{ReceivedFd, SocketAddr};
+use crate::{event, sys, Interest, Registry, Token};
+
+use std::net::Shutdown;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::path::Path;
+use std::{fmt, io};
+
+/// A non-blocking Unix domain socket server for `SOCK_SEQPACKET`
+/// connections.
+///
+/// Not available on macOS and iOS, which don't support `SOCK_SEQPACKET` Unix
+/// sockets.
+pub struct UnixSeqpacketListener {
+    inner: IoSource<ReceivedFd>,
+}
+
+impl UnixSeqpacketListener {
+    /// Creates a new `UnixSeqpacketListener` bound to the specified socket.
+    pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<UnixSeqpacketListener> {
+        sys::uds::seqpacket::bind(path.as_ref()).map(UnixSeqpacketListener::new)
+    }
+
+    /// Creates a new `UnixSeqpacketListener` bound to the specified socket
+    /// address.
+    ///
+    /// Unlike [`bind`] this can bind to an abstract address.
+    ///
+    /// [`bind`]: #method.bind
+    pub fn bind_addr(address: &SocketAddr) -> io::Result<UnixSeqpacketListener> {
+        sys::uds::seqpacket::bind_addr(address).map(UnixSeqpacketListener::new)
+    }
+
+    fn new(socket: ReceivedFd) -> UnixSeqpacketListener {
+        UnixSeqpacketListener {
+            inner: IoSource::new(socket),
+        }
+    }
+
+    /// Accepts a new incoming connection to this listener.
+    ///
+    /// The call is responsible for ensuring that the listening socket is in
+    /// non-blocking mode.
+    pub fn accept(&self) -> io::Result<(UnixSeqpacket, SocketAddr)> {
+        sys::uds::seqpacket::accept(self.inner.as_raw_fd())
+            .map(|(socket, address)| (UnixSeqpacket::new(socket), address))
+    }
+
+    /// Returns the local socket address of this listener.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        sys::uds::seqpacket::local_addr(self.inner.as_raw_fd())
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        sys::uds::seqpacket::take_error(self.inner.as_raw_fd())
+    }
+}
+
+impl event::Source for UnixSeqpacketListener {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+impl fmt::Debug for UnixSeqpacketListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl IntoRawFd for UnixSeqpacketListener {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl AsRawFd for UnixSeqpacketListener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl FromRawFd for UnixSeqpacketListener {
+    /// Converts a `RawFd` to a `UnixSeqpacketListener`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixSeqpacketListener {
+        UnixSeqpacketListener::new(ReceivedFd::from_raw_fd(fd))
+    }
+}
+
+/// A non-blocking Unix `SOCK_SEQPACKET` socket.
+///
+/// Like a [`UnixStream`] this is connection oriented, but like a
+/// [`UnixDatagram`] it preserves message boundaries: every [`send`] is
+/// received by a single [`recv`].
+///
+/// Not available on macOS and iOS, which don't support `SOCK_SEQPACKET` Unix
+/// sockets.
+///
+/// [`UnixStream`]: struct.UnixStream.html
+/// [`UnixDatagram`]: struct.UnixDatagram.html
+/// [`send`]: #method.send
+/// [`recv`]: #method.recv
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::UnixSeqpacket;
+///
+/// let (socket1, socket2) = UnixSeqpacket::pair()?;
+///
+/// socket1.send(b"hello")?;
+/// socket1.send(b"world")?;
+///
+/// // We must check if the socket is readable before calling recv, or we
+/// // could run into a WouldBlock error.
+///
+/// let mut buf = [0; 16];
+/// let (n, truncated) = socket2.recv(&mut buf)?;
+/// assert_eq!(&buf[..n], b"hello");
+/// assert!(!truncated);
+///
+/// // A buffer that is too small truncates the message, the remainder of the
+/// // message is discarded.
+/// let (n, truncated) = socket2.recv(&mut buf[..3])?;
+/// assert_eq!(&buf[..n], b"wor");
+/// assert!(truncated);
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub struct UnixSeqpacket {
+    inner: IoSource<ReceivedFd>,
+}
+
+impl UnixSeqpacket {
+    /// Connects to the socket named by `path`.
+    pub fn connect<P: AsRef<Path>>(path: P) -> io::Result<UnixSeqpacket> {
+        sys::uds::seqpacket::connect(path.as_ref()).map(UnixSeqpacket::new)
+    }
+
+    /// Connects to the socket named by `address`.
+    ///
+    /// Unlike [`connect`] this can connect to an abstract address.
+    ///
+    /// [`connect`]: #method.connect
+    pub fn connect_addr(address: &SocketAddr) -> io::Result<UnixSeqpacket> {
+        sys::uds::seqpacket::connect_addr(address).map(UnixSeqpacket::new)
+    }
+
+    /// Creates an unnamed pair of connected sockets.
+    ///
+    /// Returns two `UnixSeqpacket`s which are connected to each other.
+    pub fn pair() -> io::Result<(UnixSeqpacket, UnixSeqpacket)> {
+        sys::uds::seqpacket::pair()
+            .map(|(socket1, socket2)| (UnixSeqpacket::new(socket1), UnixSeqpacket::new(socket2)))
+    }
+
+    fn new(socket: ReceivedFd) -> UnixSeqpacket {
+        UnixSeqpacket {
+            inner: IoSource::new(socket),
+        }
+    }
+
+    /// Returns the socket address of the local half of this connection.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        sys::uds::seqpacket::local_addr(self.inner.as_raw_fd())
+    }
+
+    /// Returns the socket address of the remote half of this connection.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        sys::uds::seqpacket::peer_addr(self.inner.as_raw_fd())
+    }
+
+    /// Sends `buf` as a single message.
+    ///
+    /// On success, returns the number of bytes written, which is always the
+    /// length of `buf`.
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::seqpacket::send(inner.as_raw_fd(), buf))
+    }
+
+    /// Receives a single message into `buf`.
+    ///
+    /// On success, returns the number of bytes read and whether the message
+    /// was truncated because it didn't fit into `buf`, in which case the
+    /// rest of the message is discarded. Returns `(0, false)` both for a
+    /// zero-length message and once the peer has closed the connection, use
+    /// [`Event::is_read_closed`] to tell them apart.
+    ///
+    /// [`Event::is_read_closed`]: ../event/struct.Event.html#method.is_read_closed
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        self.inner
+            .do_io(|inner| sys::uds::seqpacket::recv(inner.as_raw_fd(), buf))
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        sys::uds::seqpacket::take_error(self.inner.as_raw_fd())
+    }
+
+    /// Shuts down the read, write, or both halves of this connection.
+    ///
+    /// This function will cause all pending and future I/O calls on the
+    /// specified portions to immediately return with an appropriate value
+    /// (see the documentation of `Shutdown`).
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        sys::uds::seqpacket::shutdown(self.inner.as_raw_fd(), how)
+    }
+}
+
+impl event::Source for UnixSeqpacket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+impl fmt::Debug for UnixSeqpacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl IntoRawFd for UnixSeqpacket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl AsRawFd for UnixSeqpacket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl FromRawFd for UnixSeqpacket {
+    /// Converts a `RawFd` to a `UnixSeqpacket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixSeqpacket {
+        UnixSeqpacket::new(ReceivedFd::from_raw_fd(fd))
+    }
+}
@@ -1,4 +1,7 @@
 use crate::io_source::IoSource;
+use crate::net::ReceivedFds;
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+use crate::net::UCred;
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::fmt;
@@ -19,6 +22,17 @@ impl UnixStream {
         sys::uds::stream::connect(path.as_ref()).map(UnixStream::from_std)
     }
 
+    /// Connects to the socket named by `address`.
+    ///
+    /// Unlike [`connect`] this can connect to an abstract address, see
+    /// [`SocketAddr::from_abstract_name`].
+    ///
+    /// [`connect`]: #method.connect
+    /// [`SocketAddr::from_abstract_name`]: struct.SocketAddr.html#method.from_abstract_name
+    pub fn connect_addr(address: &sys::SocketAddr) -> io::Result<UnixStream> {
+        sys::uds::stream::connect_addr(address).map(UnixStream::from_std)
+    }
+
     /// Creates a new `UnixStream` from a standard `net::UnixStream`.
     ///
     /// This function is intended to be used to wrap a Unix stream from the
@@ -56,6 +70,19 @@ impl UnixStream {
         sys::uds::stream::peer_addr(&self.inner)
     }
 
+    /// Returns the credentials of the process that created the other end of
+    /// this connection.
+    ///
+    /// This uses `SO_PEERCRED` on Linux and Android, which also returns the
+    /// process id, and `getpeereid(3)` on other platforms. The credentials
+    /// are those at the time the connection was established.
+    ///
+    /// Not available on Solaris and illumos.
+    #[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        sys::uds::stream::peer_cred(&self.inner)
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
@@ -69,6 +96,69 @@ impl UnixStream {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.inner.shutdown(how)
     }
+
+    /// Sends data on the socket along with the file descriptors in `fds`,
+    /// using `SCM_RIGHTS`.
+    ///
+    /// The file descriptors are duplicated into the receiving process; they
+    /// remain open in this process. On success, returns the number of bytes
+    /// written. The file descriptors are sent with the first byte, so if no
+    /// bytes are written they are not sent either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UnixStream;
+    /// use std::fs::File;
+    /// use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
+    ///
+    /// let (sender, receiver) = UnixStream::pair()?;
+    ///
+    /// let file = File::open("Cargo.toml")?;
+    /// sender.send_with_fds(b"file", &[file.as_raw_fd()])?;
+    ///
+    /// // We must check if the socket is readable before calling
+    /// // recv_with_fds, or we could run into a WouldBlock error.
+    ///
+    /// let mut buf = [0; 16];
+    /// let (n, fds) = receiver.recv_with_fds(&mut buf, 4)?;
+    /// assert_eq!(&buf[..n], b"file");
+    /// assert!(!fds.is_truncated());
+    /// for fd in fds {
+    ///     let file = unsafe { File::from_raw_fd(fd.into_raw_fd()) };
+    ///     println!("received {:?}", file);
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn send_with_fds(&self, buf: &[u8], fds: &[RawFd]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::stream::send_with_fds(inner, buf, fds))
+    }
+
+    /// Receives data on the socket along with at most `max_fds` file
+    /// descriptors passed using `SCM_RIGHTS`.
+    ///
+    /// On success, returns the number of bytes read and the file descriptors
+    /// received, which are close-on-exec and closed when dropped. If the
+    /// sender passed more than `max_fds` file descriptors
+    /// [`ReceivedFds::is_truncated`] returns `true`. On Linux and Android the
+    /// excess file descriptors are closed, on other platforms they may be
+    /// leaked, so `max_fds` should be large enough for what the sender
+    /// passes.
+    ///
+    /// [`ReceivedFds::is_truncated`]: struct.ReceivedFds.html#method.is_truncated
+    pub fn recv_with_fds(
+        &self,
+        buf: &mut [u8],
+        max_fds: usize,
+    ) -> io::Result<(usize, ReceivedFds)> {
+        self.inner
+            .do_io(|inner| sys::uds::stream::recv_with_fds(inner, buf, max_fds))
+    }
 }
 
 impl Read for UnixStream {
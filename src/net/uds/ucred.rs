@@ -0,0 +1,36 @@
+/// Credentials of a process on the other end of a Unix socket.
+///
+/// See [`UnixStream::peer_cred`] and [`UnixDatagram::recv_from_with_cred`].
+///
+/// [`UnixStream::peer_cred`]: struct.UnixStream.html#method.peer_cred
+/// [`UnixDatagram::recv_from_with_cred`]: struct.UnixDatagram.html#method.recv_from_with_cred
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UCred {
+    uid: u32,
+    gid: u32,
+    pid: Option<i32>,
+}
+
+impl UCred {
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn new(uid: u32, gid: u32, pid: Option<i32>) -> UCred {
+        UCred { uid, gid, pid }
+    }
+
+    /// The effective user id of the process.
+    pub fn uid(&self) -> u32 {
+        self.uid
+    }
+
+    /// The effective group id of the process.
+    pub fn gid(&self) -> u32 {
+        self.gid
+    }
+
+    /// The process id, if available.
+    ///
+    /// This is only available on Linux and Android.
+    pub fn pid(&self) -> Option<i32> {
+        self.pid
+    }
+}
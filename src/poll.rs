@@ -1,7 +1,11 @@
+#[cfg(all(unix, feature = "os-poll", feature = "os-util"))]
+use crate::sys::SigSet;
+use crate::token::TokenAllocator;
 use crate::{event, sys, Events, Interest, Token};
 use log::trace;
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{fmt, io};
 
@@ -92,7 +96,11 @@ use std::{fmt, io};
 /// there is no guarantee that another readiness event will be delivered, even
 /// if further data is received for the event source.
 ///
+/// This does not apply to event sources registered with [`Interest::LEVEL`],
+/// which keep returning readiness events for as long as they're ready.
+///
 /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+/// [`Interest::LEVEL`]: struct.Interest.html#associatedconstant.LEVEL
 ///
 /// ### Readiness operations
 ///
@@ -206,6 +214,8 @@ pub struct Poll {
 /// Registers I/O resources.
 pub struct Registry {
     selector: sys::Selector,
+    /// Shared between all clones of the `Registry`.
+    tokens: Arc<Mutex<TokenAllocator>>,
 }
 
 impl Poll {
@@ -236,8 +246,10 @@ impl Poll {
     /// and one with [`writable`] set.
     ///
     /// Note that the `timeout` will be rounded up to the system clock
-    /// granularity (usually 1ms), and kernel scheduling delays mean that
-    /// the blocking interval may be overrun by a small amount.
+    /// granularity, and kernel scheduling delays mean that the blocking
+    /// interval may be overrun by a small amount. On Linux, and on platforms
+    /// using kqueue, timeouts have nanosecond precision. On other platforms
+    /// the `timeout` is rounded up to whole milliseconds.
     ///
     /// See the [struct] level documentation for a higher level discussion of
     /// polling.
@@ -247,6 +259,7 @@ impl Poll {
     /// [`writable`]: struct.Interest.html#associatedconstant.WRITABLE
     /// [struct]: struct.Poll.html
     /// [`iter`]: ./event/struct.Events.html#method.iter
+    /// [`Token`]: struct.Token.html
     ///
     /// # Notes
     ///
@@ -254,6 +267,12 @@ impl Poll {
     /// of Mio would automatically retry the poll call if it was interrupted
     /// (if `EINTR` was returned).
     ///
+    /// On Linux kernels older than 5.11, which lack `epoll_pwait2`, timeouts
+    /// that aren't a whole number of milliseconds are implemented using an
+    /// internal timer registered with [`Token`]`(usize::MAX)`. Events for
+    /// event sources registered with that token are not returned by these
+    /// calls.
+    ///
     /// # Examples
     ///
     /// A basic example -- establishing a `TcpStream` connection.
@@ -349,9 +368,63 @@ cfg_os_poll! {
         /// ```
         pub fn new() -> io::Result<Poll> {
             sys::Selector::new().map(|selector| Poll {
-                registry: Registry { selector },
+                registry: Registry {
+                    selector,
+                    tokens: Arc::new(Mutex::new(TokenAllocator::default())),
+                },
             })
         }
+
+        /// Wait for readiness events, atomically replacing the signal mask of
+        /// the calling thread with `sigmask` while waiting.
+        ///
+        /// This is the same as [`poll`], but the signal mask is only replaced
+        /// while blocked in the system call, after which the original signal
+        /// mask is restored. This makes it possible to block signals in the
+        /// thread, check a flag set by a signal handler and then wait for
+        /// events while the signals are unblocked, without a signal arriving
+        /// in between being missed. If a signal handler runs while waiting an
+        /// [`Interrupted`] error is returned.
+        ///
+        /// This uses `epoll_pwait` when using epoll and `ppoll` when using
+        /// kqueue. On macOS and iOS, which don't support `ppoll`, this always
+        /// returns an error.
+        ///
+        /// [`poll`]: #method.poll
+        /// [`Interrupted`]: std::io::ErrorKind::Interrupted
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// use mio::unix::SigSet;
+        /// use mio::{Events, Poll};
+        /// use std::time::Duration;
+        ///
+        /// let mut poll = Poll::new()?;
+        /// let mut events = Events::with_capacity(8);
+        ///
+        /// // Unblock `SIGUSR1` while waiting for events.
+        /// let mut sigmask = SigSet::current()?;
+        /// sigmask.remove(libc::SIGUSR1)?;
+        /// # #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        /// poll.poll_with_sigmask(&mut events, Some(Duration::from_millis(10)), &sigmask)?;
+        /// #     Ok(())
+        /// # }
+        /// ```
+        #[cfg(all(unix, feature = "os-util"))]
+        #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-util"))))]
+        pub fn poll_with_sigmask(
+            &mut self,
+            events: &mut Events,
+            timeout: Option<Duration>,
+            sigmask: &SigSet,
+        ) -> io::Result<()> {
+            self.registry
+                .selector
+                .select_with_sigmask(events.sys(), timeout, sigmask.as_raw())
+        }
     }
 }
 
@@ -398,6 +471,11 @@ impl Registry {
     /// If a socket is registered with readable interest and the socket becomes
     /// writable, no event will be returned from [`poll`].
     ///
+    /// Next to readiness `interest` can contain registration options:
+    /// [`Interest::LEVEL`] to register the source level-triggered, rather than
+    /// the default edge-triggered, and [`Interest::ONESHOT`] to disarm the
+    /// source after a single event.
+    ///
     /// The readiness interest for an `event::Source` can be changed at any time
     /// by calling [`reregister`].
     ///
@@ -419,6 +497,8 @@ impl Registry {
     /// [`reregister`]: struct.Registry.html#method.reregister
     /// [`deregister`]: struct.Registry.html#method.deregister
     /// [`Token`]: struct.Token.html
+    /// [`Interest::LEVEL`]: struct.Interest.html#associatedconstant.LEVEL
+    /// [`Interest::ONESHOT`]: struct.Interest.html#associatedconstant.ONESHOT
     ///
     /// # Examples
     ///
@@ -546,6 +626,70 @@ impl Registry {
         source.reregister(self, token, interests)
     }
 
+    /// Rearm an [`event::Source`] registered with [`Interest::ONESHOT`].
+    ///
+    /// After an event is returned for a oneshot registration the event source
+    /// is disarmed, no more events are returned for it until it's rearmed
+    /// using this method. `interests` are always extended with
+    /// [`Interest::ONESHOT`], to switch back to a normal registration use
+    /// [`reregister`].
+    ///
+    /// Rearming is done by reregistering the event source, e.g. using
+    /// `EPOLL_CTL_MOD` on Linux, so just like `reregister` the `token` and
+    /// `interests` can be changed. If the event source is still ready when it's
+    /// rearmed, e.g. because not all data was read, a new event will be
+    /// returned.
+    ///
+    /// The event source must have previously been registered with this instance
+    /// of `Poll`, otherwise the behavior is undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use std::net;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll, Interest, Token};
+    /// use mio::net::TcpStream;
+    /// use std::net::SocketAddr;
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    ///
+    /// let address: SocketAddr = "127.0.0.1:0".parse()?;
+    /// let listener = net::TcpListener::bind(address)?;
+    /// let mut socket = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// // Register the socket with `poll`, receiving at most a single event.
+    /// poll.registry().register(
+    ///     &mut socket,
+    ///     Token(0),
+    ///     Interest::WRITABLE | Interest::ONESHOT)?;
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// // After handling the event(s) we'll want to receive more events.
+    /// poll.registry().rearm(&mut socket, Token(0), Interest::WRITABLE)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`event::Source`]: ./event/trait.Source.html
+    /// [`Interest::ONESHOT`]: struct.Interest.html#associatedconstant.ONESHOT
+    /// [`reregister`]: struct.Registry.html#method.reregister
+    pub fn rearm<S>(&self, source: &mut S, token: Token, interests: Interest) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!(
+            "rearming event source with poller: token={:?}, interests={:?}",
+            token,
+            interests
+        );
+        source.reregister(self, token, interests | Interest::ONESHOT)
+    }
+
     /// Deregister an [`event::Source`] with the `Poll` instance.
     ///
     /// When an event source is deregistered, the `Poll` instance will no longer
@@ -604,14 +748,145 @@ impl Registry {
         source.deregister(self)
     }
 
+    /// Allocate a new token.
+    ///
+    /// The returned token is unique among all allocated tokens of this
+    /// `Registry` (and its clones), until it's released using
+    /// [`release_token`]. Its [`index`] is reused once it's released, but with
+    /// a different [`generation`], making it a different token.
+    ///
+    /// Usually [`register_allocated`] is used instead, which allocates a token
+    /// and registers an event source with it.
+    ///
+    /// Allocated tokens have a non-zero generation, so they don't collide with
+    /// manually picked tokens smaller than `2^(usize::BITS / 2)`. The index
+    /// uses the lower half of the token's bits, limiting the number of
+    /// allocated tokens to `2^(usize::BITS / 2)`, which is only 65,536 on 32
+    /// bit targets. Returns an error if the maximum number of tokens is
+    /// allocated.
+    ///
+    /// [`release_token`]: #method.release_token
+    /// [`index`]: struct.Token.html#method.index
+    /// [`generation`]: struct.Token.html#method.generation
+    /// [`register_allocated`]: #method.register_allocated
+    pub fn allocate_token(&self) -> io::Result<Token> {
+        self.tokens.lock().unwrap().allocate()
+    }
+
+    /// Release a `token` allocated by [`allocate_token`].
+    ///
+    /// Returns false if `token` wasn't allocated, e.g. because it was already
+    /// released.
+    ///
+    /// [`allocate_token`]: #method.allocate_token
+    pub fn release_token(&self, token: Token) -> bool {
+        self.tokens.lock().unwrap().release(token)
+    }
+
+    /// Returns true if `token` is allocated, i.e. it was returned by
+    /// [`allocate_token`] and not yet released.
+    ///
+    /// This can be used to detect stale events: a single call to [`poll`] can
+    /// return an event for an event source that is deregistered (and its token
+    /// released) while handling an earlier event in the same batch.
+    ///
+    /// [`allocate_token`]: #method.allocate_token
+    /// [`poll`]: struct.Poll.html#method.poll
+    pub fn is_token_allocated(&self, token: Token) -> bool {
+        self.tokens.lock().unwrap().is_allocated(token)
+    }
+
+    /// Register an event source using an allocated token.
+    ///
+    /// This allocates a token, see [`allocate_token`], and registers `source`
+    /// with it, see [`register`]. The token is released if registering fails.
+    /// Use [`deregister_allocated`] to deregister the event source and release
+    /// the token.
+    ///
+    /// [`allocate_token`]: #method.allocate_token
+    /// [`register`]: #method.register
+    /// [`deregister_allocated`]: #method.deregister_allocated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll, Interest};
+    /// use mio::net::TcpListener;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    /// let mut listeners = HashMap::new();
+    ///
+    /// let mut listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    /// let token = poll.registry().register_allocated(&mut listener, Interest::READABLE)?;
+    /// listeners.insert(token.index(), (token, listener));
+    ///
+    /// # let address = listeners[&token.index()].1.local_addr()?;
+    /// # let _stream = std::net::TcpStream::connect(address)?;
+    /// poll.poll(&mut events, None)?;
+    ///
+    /// for event in events.iter() {
+    ///     // Drop events for deregistered event sources.
+    ///     if !poll.registry().is_token_allocated(event.token()) {
+    ///         continue;
+    ///     }
+    ///     let (_, listener) = &listeners[&event.token().index()];
+    ///     // Accept connections...
+    ///     # let _ = listener.accept()?;
+    /// }
+    ///
+    /// let (token, mut listener) = listeners.remove(&token.index()).unwrap();
+    /// poll.registry().deregister_allocated(&mut listener, token)?;
+    /// assert!(!poll.registry().is_token_allocated(token));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_allocated<S>(&self, source: &mut S, interests: Interest) -> io::Result<Token>
+    where
+        S: event::Source + ?Sized,
+    {
+        let token = self.allocate_token()?;
+        if let Err(err) = self.register(source, token, interests) {
+            let _ = self.release_token(token);
+            return Err(err);
+        }
+        Ok(token)
+    }
+
+    /// Deregister an event source registered using [`register_allocated`] and
+    /// release its `token`.
+    ///
+    /// `token` must be the token `source` is currently registered with, i.e.
+    /// the token returned by [`register_allocated`] or the allocated token
+    /// passed to the last call to [`reregister`]. It's only released if
+    /// deregistering succeeds. Using [`deregister`] doesn't release the
+    /// token, it must be released using [`release_token`] instead.
+    ///
+    /// [`register_allocated`]: #method.register_allocated
+    /// [`reregister`]: #method.reregister
+    /// [`deregister`]: #method.deregister
+    /// [`release_token`]: #method.release_token
+    pub fn deregister_allocated<S>(&self, source: &mut S, token: Token) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        self.deregister(source).map(|()| {
+            let _ = self.release_token(token);
+        })
+    }
+
     /// Creates a new independently owned `Registry`.
     ///
     /// Event sources registered with this `Registry` will be registered with
     /// the original `Registry` and `Poll` instance.
     pub fn try_clone(&self) -> io::Result<Registry> {
-        self.selector
-            .try_clone()
-            .map(|selector| Registry { selector })
+        self.selector.try_clone().map(|selector| Registry {
+            selector,
+            tokens: self.tokens.clone(),
+        })
     }
 }
 
@@ -56,6 +56,14 @@ cfg_os_poll! {
     mod unix;
     pub use self::unix::SourceFd;
 
+    #[cfg(all(feature = "os-util", any(target_os = "android", target_os = "linux")))]
+    pub use self::unix::{
+        Clock, Inotify, InotifyEvent, Process, Timer, WatchDescriptor, WatchEvent,
+    };
+
+    #[cfg(feature = "os-util")]
+    pub use self::unix::{pipe, Pending, SigSet, SignalInfo, Signals};
+
     pub(crate) use self::unix::{event, Event, Events, Selector, Waker};
 
     cfg_tcp! {
@@ -66,6 +74,19 @@ cfg_os_poll! {
         pub(crate) use self::unix::udp;
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    cfg_udp! {
+        pub(crate) use self::unix::icmp;
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg(any(feature = "tcp", feature = "udp"))]
+    pub(crate) use self::unix::reuseport;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg(any(feature = "tcp", feature = "udp"))]
+    pub(crate) use self::unix::timestamp;
+
     cfg_uds! {
         pub use self::unix::SocketAddr;
 
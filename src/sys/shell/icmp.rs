@@ -0,0 +1,6 @@
+use std::io;
+use std::net::{self, SocketAddr};
+
+pub(crate) fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
+    os_required!()
+}
@@ -18,6 +18,15 @@ cfg_udp! {
     pub(crate) mod udp;
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+cfg_udp! {
+    pub(crate) mod icmp;
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg(any(feature = "tcp", feature = "udp"))]
+pub(crate) mod timestamp;
+
 #[cfg(unix)]
 cfg_uds! {
     pub(crate) mod uds;
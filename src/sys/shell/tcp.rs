@@ -1,11 +1,147 @@
 use std::io;
 use std::net::{self, SocketAddr};
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+use std::time::Duration;
 
-pub fn connect(_: SocketAddr) -> io::Result<net::TcpStream> {
+#[cfg(unix)]
+pub(crate) type TcpSocket = i32;
+#[cfg(windows)]
+pub(crate) type TcpSocket = usize;
+
+pub(crate) fn new_v4_socket() -> io::Result<TcpSocket> {
+    os_required!();
+}
+
+pub(crate) fn new_v6_socket() -> io::Result<TcpSocket> {
+    os_required!();
+}
+
+pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<TcpSocket> {
+    os_required!();
+}
+
+pub(crate) fn bind(_: TcpSocket, _: SocketAddr) -> io::Result<()> {
+    os_required!();
+}
+
+pub(crate) fn connect(_: TcpSocket, _: SocketAddr) -> io::Result<net::TcpStream> {
+    os_required!();
+}
+
+pub(crate) fn connect_status(_: &net::TcpStream) -> io::Result<bool> {
+    os_required!();
+}
+
+pub(crate) fn listen(_: TcpSocket, _: u32) -> io::Result<net::TcpListener> {
+    os_required!();
+}
+
+pub(crate) fn close(_: TcpSocket) {
+    os_required!();
+}
+
+pub(crate) fn set_reuseaddr(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+pub(crate) fn get_reuseaddr(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn set_reuseport(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn get_reuseport(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+pub(crate) fn set_send_buffer_size(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+pub(crate) fn get_send_buffer_size(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+pub(crate) fn set_recv_buffer_size(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+pub(crate) fn get_recv_buffer_size(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+pub(crate) fn set_keepalive(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+pub(crate) fn get_keepalive(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_keepalive_time(_: TcpSocket, _: Duration) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_keepalive_time(_: TcpSocket) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_keepalive_interval(_: TcpSocket, _: Duration) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_keepalive_interval(_: TcpSocket) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_keepalive_retries(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_keepalive_retries(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_user_timeout(_: TcpSocket, _: Option<Duration>) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn get_user_timeout(_: TcpSocket) -> io::Result<Option<Duration>> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_fastopen(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn get_fastopen(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn connect_with_data(
+    _: TcpSocket,
+    _: SocketAddr,
+    _: &[u8],
+) -> io::Result<(net::TcpStream, usize)> {
     os_required!();
 }
 
-pub fn bind(_: SocketAddr) -> io::Result<net::TcpListener> {
+pub(crate) fn get_localaddr(_: TcpSocket) -> io::Result<SocketAddr> {
     os_required!();
 }
 
@@ -0,0 +1,34 @@
+use crate::net::TxTimestamp;
+
+use std::io;
+use std::os::unix::io::RawFd;
+#[cfg(feature = "tcp")]
+use std::time::SystemTime;
+
+pub(crate) fn set_recv_timestamps(_: RawFd, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+pub(crate) fn recv_timestamps(_: RawFd) -> io::Result<bool> {
+    os_required!()
+}
+
+pub(crate) fn set_tx_timestamps(_: RawFd, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+pub(crate) fn tx_timestamps(_: RawFd) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(feature = "tcp")]
+pub(crate) fn recv_timestamped(
+    _: RawFd,
+    _: &mut [u8],
+) -> io::Result<(usize, libc::sockaddr_storage, Option<SystemTime>)> {
+    os_required!()
+}
+
+pub(crate) fn recv_tx_timestamp(_: RawFd) -> io::Result<TxTimestamp> {
+    os_required!()
+}
@@ -1,6 +1,127 @@
 use std::io;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{self, SocketAddr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::SystemTime;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::PacketInfo;
+#[cfg(unix)]
+use crate::net::{RecvMsg, SendMsg};
 
 pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
     os_required!()
 }
+
+#[cfg(unix)]
+pub(crate) fn send_vectored(
+    _: &net::UdpSocket,
+    _: &[IoSlice<'_>],
+    _: Option<&SocketAddr>,
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_vectored(
+    _: &net::UdpSocket,
+    _: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_vectored_connected(
+    _: &net::UdpSocket,
+    _: &mut [IoSliceMut<'_>],
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn send_batch(_: &net::UdpSocket, _: &[SendMsg<'_, SocketAddr>]) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_batch(
+    _: &net::UdpSocket,
+    _: &mut [RecvMsg<'_, SocketAddr>],
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_segmented(
+    _: &net::UdpSocket,
+    _: &[u8],
+    _: u16,
+    _: Option<&SocketAddr>,
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_gro(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_gro(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<u16>)> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn probe_offload() -> (bool, bool) {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_pktinfo_v4(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn pktinfo_v4(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_pktinfo_v6(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo_v6(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_pktinfo(
+    _: &net::UdpSocket,
+    _: &[u8],
+    _: &SocketAddr,
+    _: &PacketInfo,
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<PacketInfo>)> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_timestamped(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<SystemTime>)> {
+    os_required!()
+}
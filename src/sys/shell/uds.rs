@@ -1,6 +1,9 @@
 pub(crate) mod datagram {
-    use crate::net::SocketAddr;
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    use crate::net::UCred;
+    use crate::net::{ReceivedFds, RecvMsg, SendMsg, SocketAddr};
     use std::io;
+    use std::os::unix::io::RawFd;
     use std::os::unix::net;
     use std::path::Path;
 
@@ -8,6 +11,22 @@ pub(crate) mod datagram {
         os_required!()
     }
 
+    pub(crate) fn bind_addr(_: &SocketAddr) -> io::Result<net::UnixDatagram> {
+        os_required!()
+    }
+
+    pub(crate) fn connect_addr(_: &net::UnixDatagram, _: &SocketAddr) -> io::Result<()> {
+        os_required!()
+    }
+
+    pub(crate) fn send_to_addr(
+        _: &net::UnixDatagram,
+        _: &[u8],
+        _: &SocketAddr,
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
     pub(crate) fn unbound() -> io::Result<net::UnixDatagram> {
         os_required!()
     }
@@ -30,6 +49,46 @@ pub(crate) mod datagram {
     ) -> io::Result<(usize, SocketAddr)> {
         os_required!()
     }
+    pub(crate) fn send_batch(_: &net::UnixDatagram, _: &[SendMsg<'_, &Path>]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_batch(
+        _: &net::UnixDatagram,
+        _: &mut [RecvMsg<'_, SocketAddr>],
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn send_with_fds(_: &net::UnixDatagram, _: &[u8], _: &[RawFd]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_with_fds(
+        _: &net::UnixDatagram,
+        _: &mut [u8],
+        _: usize,
+    ) -> io::Result<(usize, ReceivedFds)> {
+        os_required!()
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn set_passcred(_: &net::UnixDatagram, _: bool) -> io::Result<()> {
+        os_required!()
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn passcred(_: &net::UnixDatagram) -> io::Result<bool> {
+        os_required!()
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn recv_from_with_cred(
+        _: &net::UnixDatagram,
+        _: &mut [u8],
+    ) -> io::Result<(usize, SocketAddr, Option<UCred>)> {
+        os_required!()
+    }
 }
 
 pub(crate) mod listener {
@@ -42,6 +101,10 @@ pub(crate) mod listener {
         os_required!()
     }
 
+    pub(crate) fn bind_addr(_: &SocketAddr) -> io::Result<net::UnixListener> {
+        os_required!()
+    }
+
     pub(crate) fn accept(_: &net::UnixListener) -> io::Result<(UnixStream, SocketAddr)> {
         os_required!()
     }
@@ -51,9 +114,69 @@ pub(crate) mod listener {
     }
 }
 
-pub(crate) mod stream {
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+pub(crate) mod seqpacket {
     use crate::net::SocketAddr;
     use std::io;
+    use std::net::Shutdown;
+    use std::os::unix::io::{FromRawFd, RawFd};
+    use std::path::Path;
+
+    pub(crate) fn bind<T: FromRawFd>(_: &Path) -> io::Result<T> {
+        os_required!()
+    }
+
+    pub(crate) fn bind_addr<T: FromRawFd>(_: &SocketAddr) -> io::Result<T> {
+        os_required!()
+    }
+
+    pub(crate) fn accept<T: FromRawFd>(_: RawFd) -> io::Result<(T, SocketAddr)> {
+        os_required!()
+    }
+
+    pub(crate) fn connect<T: FromRawFd>(_: &Path) -> io::Result<T> {
+        os_required!()
+    }
+
+    pub(crate) fn connect_addr<T: FromRawFd>(_: &SocketAddr) -> io::Result<T> {
+        os_required!()
+    }
+
+    pub(crate) fn pair<T: FromRawFd>() -> io::Result<(T, T)> {
+        os_required!()
+    }
+
+    pub(crate) fn local_addr(_: RawFd) -> io::Result<SocketAddr> {
+        os_required!()
+    }
+
+    pub(crate) fn peer_addr(_: RawFd) -> io::Result<SocketAddr> {
+        os_required!()
+    }
+
+    pub(crate) fn send(_: RawFd, _: &[u8]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv(_: RawFd, _: &mut [u8]) -> io::Result<(usize, bool)> {
+        os_required!()
+    }
+
+    pub(crate) fn shutdown(_: RawFd, _: Shutdown) -> io::Result<()> {
+        os_required!()
+    }
+
+    pub(crate) fn take_error(_: RawFd) -> io::Result<Option<io::Error>> {
+        os_required!()
+    }
+}
+
+pub(crate) mod stream {
+    #[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+    use crate::net::UCred;
+    use crate::net::{ReceivedFds, SocketAddr};
+    use std::io;
+    use std::os::unix::io::RawFd;
     use std::os::unix::net;
     use std::path::Path;
 
@@ -61,6 +184,10 @@ pub(crate) mod stream {
         os_required!()
     }
 
+    pub(crate) fn connect_addr(_: &SocketAddr) -> io::Result<net::UnixStream> {
+        os_required!()
+    }
+
     pub(crate) fn pair() -> io::Result<(net::UnixStream, net::UnixStream)> {
         os_required!()
     }
@@ -72,4 +199,21 @@ pub(crate) mod stream {
     pub(crate) fn peer_addr(_: &net::UnixStream) -> io::Result<SocketAddr> {
         os_required!()
     }
+
+    pub(crate) fn send_with_fds(_: &net::UnixStream, _: &[u8], _: &[RawFd]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_with_fds(
+        _: &net::UnixStream,
+        _: &mut [u8],
+        _: usize,
+    ) -> io::Result<(usize, ReceivedFds)> {
+        os_required!()
+    }
+
+    #[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+    pub(crate) fn peer_cred(_: &net::UnixStream) -> io::Result<UCred> {
+        os_required!()
+    }
 }
@@ -0,0 +1,145 @@
+//! Helpers to encode and decode control (ancillary) messages passed to
+//! `sendmsg(2)` and returned by `recvmsg(2)`.
+
+// Not all helpers are used with every combination of features.
+#![allow(dead_code)]
+
+use std::mem::size_of;
+use std::{cmp, ptr, slice};
+
+/// Number of bytes a control message with `len` bytes of data takes up in a
+/// control buffer, including padding.
+pub(crate) fn space(len: usize) -> usize {
+    unsafe { libc::CMSG_SPACE(len as libc::c_uint) as usize }
+}
+
+/// Length of a control message with `len` bytes of data, without the
+/// trailing padding.
+pub(crate) fn len(len: usize) -> usize {
+    unsafe { libc::CMSG_LEN(len as libc::c_uint) as usize }
+}
+
+/// A control buffer, suitably aligned for `cmsghdr`.
+#[derive(Debug)]
+pub(crate) struct Buffer {
+    // `u64` is at least as strictly aligned as `cmsghdr` on all supported
+    // platforms.
+    storage: Vec<u64>,
+    len: usize,
+}
+
+impl Buffer {
+    /// Create an empty buffer to encode control messages into.
+    pub(crate) fn new() -> Buffer {
+        Buffer {
+            storage: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Create a zeroed buffer of `capacity` bytes to receive control messages
+    /// into.
+    pub(crate) fn with_capacity(capacity: usize) -> Buffer {
+        Buffer {
+            storage: vec![0; words(capacity)],
+            len: capacity,
+        }
+    }
+
+    /// Append a control message with `level`, `ty` and `data`.
+    pub(crate) fn push(&mut self, level: libc::c_int, ty: libc::c_int, data: &[u8]) {
+        let start = self.len;
+        self.len += space(data.len());
+        self.storage.resize(words(self.len), 0);
+        unsafe {
+            let cmsg = (self.storage.as_mut_ptr() as *mut u8).add(start) as *mut libc::cmsghdr;
+            (*cmsg).cmsg_level = level;
+            (*cmsg).cmsg_type = ty;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(data.len() as libc::c_uint) as _;
+            ptr::copy_nonoverlapping(data.as_ptr(), libc::CMSG_DATA(cmsg), data.len());
+        }
+    }
+
+    /// Append a control message with `level`, `ty` and the bytes of `value`.
+    pub(crate) fn push_value<T: Copy>(&mut self, level: libc::c_int, ty: libc::c_int, value: T) {
+        let data =
+            unsafe { slice::from_raw_parts(&value as *const T as *const u8, size_of::<T>()) };
+        self.push(level, ty, data)
+    }
+
+    /// Point the control fields of `msg` at this buffer.
+    pub(crate) fn apply(&mut self, msg: &mut libc::msghdr) {
+        if self.len == 0 {
+            msg.msg_control = ptr::null_mut();
+        } else {
+            msg.msg_control = self.storage.as_mut_ptr() as *mut libc::c_void;
+        }
+        msg.msg_controllen = self.len as _;
+    }
+}
+
+/// Number of `u64`s needed to store `bytes` bytes, with room to spare.
+fn words(bytes: usize) -> usize {
+    bytes / size_of::<u64>() + 1
+}
+
+/// Iterate over the control messages returned in `msg` as
+/// `(level, type, data)`.
+///
+/// # Safety
+///
+/// `msg` must have been filled in by a successful call to `recvmsg(2)` and
+/// its control buffer must still be alive.
+pub(crate) unsafe fn messages(msg: &libc::msghdr) -> Messages<'_> {
+    Messages {
+        msg,
+        cmsg: libc::CMSG_FIRSTHDR(msg),
+    }
+}
+
+/// Iterator returned by [`messages`].
+pub(crate) struct Messages<'a> {
+    msg: &'a libc::msghdr,
+    cmsg: *mut libc::cmsghdr,
+}
+
+impl<'a> Iterator for Messages<'a> {
+    type Item = (libc::c_int, libc::c_int, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cmsg.is_null() {
+            return None;
+        }
+        unsafe {
+            // Don't trust `cmsg_len`, if the control data was truncated
+            // (`MSG_CTRUNC`) some platforms leave it set to the length of the
+            // untruncated message.
+            // `msg_controllen` is not a `usize` on all platforms.
+            #[allow(clippy::unnecessary_cast)]
+            let end = (self.msg.msg_control as *const u8).add(self.msg.msg_controllen as usize);
+            let header = libc::CMSG_LEN(0) as usize;
+            let data_ptr = libc::CMSG_DATA(self.cmsg) as *const u8;
+            if data_ptr > end || (self.cmsg as *const u8).add(header) > end {
+                self.cmsg = ptr::null_mut();
+                return None;
+            }
+            let cmsg = &*self.cmsg;
+            // `cmsg_len` is not a `usize` on all platforms.
+            #[allow(clippy::unnecessary_cast)]
+            let len = (cmsg.cmsg_len as usize).saturating_sub(header);
+            let len = cmp::min(len, end as usize - data_ptr as usize);
+            let data = slice::from_raw_parts(data_ptr, len);
+            self.cmsg = libc::CMSG_NXTHDR(self.msg, cmsg);
+            Some((cmsg.cmsg_level, cmsg.cmsg_type, data))
+        }
+    }
+}
+
+/// Read a `T` from the start of control message data `data`.
+pub(crate) fn read<T: Copy>(data: &[u8]) -> Option<T> {
+    if data.len() < size_of::<T>() {
+        None
+    } else {
+        Some(unsafe { ptr::read_unaligned(data.as_ptr() as *const T) })
+    }
+}
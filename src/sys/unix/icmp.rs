@@ -0,0 +1,24 @@
+use crate::sys::unix::net::{new_ip_socket, socket_addr};
+
+use std::io;
+use std::net::{self, SocketAddr};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+
+/// Create an ICMP (or ICMPv6) "ping" socket and bind it to `addr`.
+///
+/// Ping sockets are datagram sockets, so the standard library's `UdpSocket`
+/// is used to own the socket and to send and receive on it.
+pub(crate) fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
+    let protocol = match addr {
+        SocketAddr::V4(..) => libc::IPPROTO_ICMP,
+        SocketAddr::V6(..) => libc::IPPROTO_ICMPV6,
+    };
+
+    // Ensure the socket is closed if binding fails.
+    let socket = new_ip_socket(addr, libc::SOCK_DGRAM, protocol)
+        .map(|socket| unsafe { net::UdpSocket::from_raw_fd(socket) })?;
+
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(bind(socket.as_raw_fd(), raw_addr, raw_addr_length))?;
+    Ok(socket)
+}
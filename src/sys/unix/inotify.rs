@@ -0,0 +1,287 @@
+use crate::sys::unix::SourceFd;
+use crate::{event, Interest, Registry, Token};
+
+use std::collections::HashMap;
+use std::ffi::{CString, OsStr, OsString};
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::path::{Path, PathBuf};
+use std::{mem, ptr};
+
+/// Size of the buffer used to read events, large enough to hold at least one
+/// event with the longest possible name (`NAME_MAX`).
+const BUF_SIZE: usize = 4096;
+
+/// Size of the `inotify_event` header, the (variable sized) name follows it.
+const HEADER_SIZE: usize = mem::size_of::<libc::inotify_event>();
+
+/// File system notifications backed by `inotify`.
+///
+/// `Inotify` implements [`event::Source`], it becomes readable once an event
+/// for one of its watches is queued. After receiving a readable event the
+/// events can be read using [`read_events`].
+///
+/// Watches are added using [`add_watch`] with a mask of events to watch for,
+/// using the `IN_*` constants from the `libc` crate, e.g. `libc::IN_MODIFY`.
+///
+/// [`event::Source`]: ../event/trait.Source.html
+/// [`read_events`]: #method.read_events
+/// [`add_watch`]: #method.add_watch
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::{Inotify, InotifyEvent};
+/// use mio::{Events, Interest, Poll, Token};
+/// use std::fs::File;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let dir = std::env::temp_dir().join("mio_inotify_example");
+/// # let _ = std::fs::remove_dir_all(&dir);
+/// std::fs::create_dir(&dir)?;
+///
+/// let mut inotify = Inotify::new()?;
+/// inotify.add_watch(&dir, libc::IN_CREATE)?;
+/// poll.registry().register(&mut inotify, Token(0), Interest::READABLE)?;
+///
+/// File::create(dir.join("config.toml"))?;
+///
+/// poll.poll(&mut events, None)?;
+/// for event in inotify.read_events()? {
+///     match event {
+///         InotifyEvent::Watch(event) => {
+///             assert_eq!(event.path(), Some(&*dir.join("config.toml")));
+///             assert!(event.mask() & libc::IN_CREATE != 0);
+///         }
+///         InotifyEvent::Overflow => println!("events were lost"),
+///     }
+/// }
+/// # std::fs::remove_dir_all(&dir)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Inotify {
+    fd: File,
+    buf: Vec<u8>,
+    /// Path per watch, used to determine the path of events.
+    watches: HashMap<WatchDescriptor, PathBuf>,
+}
+
+/// Identifier of a watch, returned by [`Inotify::add_watch`].
+///
+/// [`Inotify::add_watch`]: struct.Inotify.html#method.add_watch
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct WatchDescriptor(libc::c_int);
+
+/// Event returned by [`Inotify::read_events`].
+///
+/// [`Inotify::read_events`]: struct.Inotify.html#method.read_events
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InotifyEvent {
+    /// Event for a watch.
+    Watch(WatchEvent),
+    /// The event queue overflowed (`IN_Q_OVERFLOW`), events were lost.
+    Overflow,
+}
+
+/// Event for a watch, see [`InotifyEvent`].
+///
+/// [`InotifyEvent`]: enum.InotifyEvent.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WatchEvent {
+    watch: WatchDescriptor,
+    mask: u32,
+    cookie: u32,
+    name: Option<OsString>,
+    path: Option<PathBuf>,
+}
+
+impl WatchEvent {
+    /// The watch this event is for.
+    pub fn watch(&self) -> WatchDescriptor {
+        self.watch
+    }
+
+    /// Mask describing the event, e.g. `libc::IN_MODIFY`.
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Cookie connecting related events, e.g. an `IN_MOVED_FROM` event and
+    /// the related `IN_MOVED_TO` event. Zero for unrelated events.
+    pub fn cookie(&self) -> u32 {
+        self.cookie
+    }
+
+    /// Name of the file, relative to the watched directory, if the event is
+    /// for a file inside a watched directory.
+    pub fn name(&self) -> Option<&OsStr> {
+        self.name.as_ref().map(|n| n.as_os_str())
+    }
+
+    /// Path of the file the event is for, i.e. the watched path joined with
+    /// the [`name`].
+    ///
+    /// Returns `None` if the watch is unknown, e.g. when the watch was added
+    /// using a different `Inotify` (sharing the same file descriptor).
+    ///
+    /// [`name`]: #method.name
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_ref().map(|p| p.as_path())
+    }
+}
+
+impl Inotify {
+    /// Create a new `Inotify` without any watches.
+    pub fn new() -> io::Result<Inotify> {
+        syscall!(inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC)).map(|fd| Inotify {
+            fd: unsafe { File::from_raw_fd(fd) },
+            buf: vec![0; BUF_SIZE],
+            watches: HashMap::new(),
+        })
+    }
+
+    /// Add a watch for `path`, or modify the existing watch for `path`,
+    /// watching for the events in `mask`.
+    pub fn add_watch<P: AsRef<Path>>(&mut self, path: P, mask: u32) -> io::Result<WatchDescriptor> {
+        let path = path.as_ref();
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        syscall!(inotify_add_watch(
+            self.fd.as_raw_fd(),
+            c_path.as_ptr(),
+            mask
+        ))
+        .map(|wd| {
+            let watch = WatchDescriptor(wd);
+            let _ = self.watches.insert(watch, path.to_path_buf());
+            watch
+        })
+    }
+
+    /// Remove the `watch`.
+    ///
+    /// This generates an `IN_IGNORED` event for the watch.
+    pub fn rm_watch(&mut self, watch: WatchDescriptor) -> io::Result<()> {
+        syscall!(inotify_rm_watch(self.fd.as_raw_fd(), watch.0)).map(|_| ())
+    }
+
+    /// Read all queued events.
+    ///
+    /// Returns an empty vector if no events are queued.
+    pub fn read_events(&mut self) -> io::Result<Vec<InotifyEvent>> {
+        let mut events = Vec::new();
+        loop {
+            let n = match (&self.fd).read(&mut self.buf) {
+                Ok(n) => n,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(events),
+                Err(err) => return Err(err),
+            };
+
+            let mut offset = 0;
+            while offset + HEADER_SIZE <= n {
+                // The buffer is not aligned for `inotify_event`.
+                let event: libc::inotify_event =
+                    unsafe { ptr::read_unaligned(self.buf[offset..].as_ptr() as *const _) };
+                let name_start = offset + HEADER_SIZE;
+                offset = name_start + event.len as usize;
+
+                if event.mask & libc::IN_Q_OVERFLOW != 0 {
+                    events.push(InotifyEvent::Overflow);
+                    continue;
+                }
+
+                // The name is padded with null bytes.
+                let name = &self.buf[name_start..offset];
+                let name = match name.iter().position(|&b| b == 0) {
+                    Some(0) => None,
+                    Some(end) => Some(&name[..end]),
+                    None if name.is_empty() => None,
+                    None => Some(name),
+                }
+                .map(|name| OsString::from_vec(name.to_vec()));
+
+                let watch = WatchDescriptor(event.wd);
+                let path = self.watches.get(&watch).map(|path| match name {
+                    Some(ref name) => path.join(name),
+                    None => path.clone(),
+                });
+                if event.mask & libc::IN_IGNORED != 0 {
+                    // Watch was removed, explicitly or because the file was
+                    // deleted.
+                    let _ = self.watches.remove(&watch);
+                }
+
+                events.push(InotifyEvent::Watch(WatchEvent {
+                    watch,
+                    mask: event.mask,
+                    cookie: event.cookie,
+                    name,
+                    path,
+                }));
+            }
+        }
+    }
+}
+
+impl event::Source for Inotify {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl AsRawFd for Inotify {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Inotify {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
+
+impl FromRawFd for Inotify {
+    /// Converts a `RawFd` to an `Inotify`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the fd is an `inotify`
+    /// instance in non-blocking mode. Events for watches added before the
+    /// conversion will not have a [`path`].
+    ///
+    /// [`path`]: struct.WatchEvent.html#method.path
+    unsafe fn from_raw_fd(fd: RawFd) -> Inotify {
+        Inotify {
+            fd: File::from_raw_fd(fd),
+            buf: vec![0; BUF_SIZE],
+            watches: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Sending and receiving batches of datagrams, using `sendmmsg(2)` and
+//! `recvmmsg(2)` where available and a loop over `sendmsg(2)` and
+//! `recvmsg(2)` otherwise.
+
+use std::io;
+use std::mem::{self, size_of};
+use std::os::unix::io::RawFd;
+
+use crate::net::{RecvMsg, SendMsg};
+
+/// Sends `msgs`, returning the number of datagrams sent.
+///
+/// `raw_addr` converts the address of a message into a raw socket address.
+pub(crate) fn send_batch<A, F>(
+    socket: RawFd,
+    msgs: &[SendMsg<'_, A>],
+    mut raw_addr: F,
+) -> io::Result<usize>
+where
+    F: FnMut(&A) -> io::Result<(libc::sockaddr_storage, libc::socklen_t)>,
+{
+    let mut addrs = Vec::with_capacity(msgs.len());
+    for msg in msgs {
+        addrs.push(match msg.addr {
+            Some(ref addr) => Some(raw_addr(addr)?),
+            None => None,
+        });
+    }
+    let mut iovs: Vec<libc::iovec> = msgs
+        .iter()
+        .map(|msg| libc::iovec {
+            iov_base: msg.buf.as_ptr() as *mut libc::c_void,
+            iov_len: msg.buf.len(),
+        })
+        .collect();
+    let mut hdrs: Vec<libc::msghdr> = iovs
+        .iter_mut()
+        .zip(addrs.iter_mut())
+        .map(|(iov, addr)| {
+            let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+            if let Some((ref mut storage, len)) = addr {
+                hdr.msg_name = storage as *mut libc::sockaddr_storage as *mut libc::c_void;
+                hdr.msg_namelen = *len;
+            }
+            hdr.msg_iov = iov;
+            hdr.msg_iovlen = 1;
+            hdr
+        })
+        .collect();
+
+    send(socket, &mut hdrs)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send(socket: RawFd, hdrs: &mut [libc::msghdr]) -> io::Result<usize> {
+    if hdrs.is_empty() {
+        return Ok(0);
+    }
+
+    let mut mmsgs: Vec<libc::mmsghdr> = hdrs
+        .iter()
+        .map(|&msg_hdr| libc::mmsghdr {
+            msg_hdr,
+            msg_len: 0,
+        })
+        .collect();
+    syscall!(sendmmsg(
+        socket,
+        mmsgs.as_mut_ptr(),
+        mmsgs.len() as libc::c_uint,
+        0
+    ))
+    .map(|n| n as usize)
+}
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn send(socket: RawFd, hdrs: &mut [libc::msghdr]) -> io::Result<usize> {
+    for (n, hdr) in hdrs.iter().enumerate() {
+        match syscall!(sendmsg(socket, hdr, 0)) {
+            Ok(_) => {}
+            // Like `sendmmsg(2)` only return an error if the first datagram
+            // couldn't be sent.
+            Err(err) if n == 0 => return Err(err),
+            Err(_) => return Ok(n),
+        }
+    }
+    Ok(hdrs.len())
+}
+
+/// Receives datagrams into `msgs`, returning the number of datagrams
+/// received.
+///
+/// `addr` converts the raw socket address of the sender into an address. If
+/// that fails for a datagram other than the first, the number of datagrams
+/// before it is returned.
+pub(crate) fn recv_batch<A, F>(
+    socket: RawFd,
+    msgs: &mut [RecvMsg<'_, A>],
+    mut addr: F,
+) -> io::Result<usize>
+where
+    F: FnMut(&libc::sockaddr_storage, libc::socklen_t) -> io::Result<A>,
+{
+    let mut storages: Vec<libc::sockaddr_storage> = vec![unsafe { mem::zeroed() }; msgs.len()];
+    let mut iovs: Vec<libc::iovec> = msgs
+        .iter_mut()
+        .map(|msg| libc::iovec {
+            iov_base: msg.buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: msg.buf.len(),
+        })
+        .collect();
+    let mut hdrs: Vec<libc::msghdr> = iovs
+        .iter_mut()
+        .zip(storages.iter_mut())
+        .map(|(iov, storage)| {
+            let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+            hdr.msg_name = storage as *mut libc::sockaddr_storage as *mut libc::c_void;
+            hdr.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            hdr.msg_iov = iov;
+            hdr.msg_iovlen = 1;
+            hdr
+        })
+        .collect();
+
+    let lens = recv(socket, &mut hdrs)?;
+    for (i, len) in lens.iter().enumerate() {
+        let msg_addr = match addr(&storages[i], hdrs[i].msg_namelen) {
+            Ok(msg_addr) => msg_addr,
+            // Like `send_batch` only return an error for the first datagram,
+            // so the datagrams before it aren't lost.
+            Err(err) if i == 0 => return Err(err),
+            Err(_) => return Ok(i),
+        };
+        let msg = &mut msgs[i];
+        msg.len = *len;
+        msg.truncated = hdrs[i].msg_flags & libc::MSG_TRUNC != 0;
+        msg.addr = Some(msg_addr);
+    }
+    Ok(lens.len())
+}
+
+/// Returns the length of each received datagram.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv(socket: RawFd, hdrs: &mut [libc::msghdr]) -> io::Result<Vec<usize>> {
+    if hdrs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut mmsgs: Vec<libc::mmsghdr> = hdrs
+        .iter()
+        .map(|&msg_hdr| libc::mmsghdr {
+            msg_hdr,
+            msg_len: 0,
+        })
+        .collect();
+    let n = syscall!(recvmmsg(
+        socket,
+        mmsgs.as_mut_ptr(),
+        mmsgs.len() as libc::c_uint,
+        0,
+        std::ptr::null_mut(),
+    ))? as usize;
+    // Copy back the fields set by the kernel.
+    for (hdr, mmsg) in hdrs.iter_mut().zip(&mmsgs[..n]) {
+        hdr.msg_namelen = mmsg.msg_hdr.msg_namelen;
+        hdr.msg_flags = mmsg.msg_hdr.msg_flags;
+    }
+    Ok(mmsgs[..n]
+        .iter()
+        .map(|mmsg| mmsg.msg_len as usize)
+        .collect())
+}
+
+/// Returns the length of each received datagram.
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn recv(socket: RawFd, hdrs: &mut [libc::msghdr]) -> io::Result<Vec<usize>> {
+    let mut lens = Vec::with_capacity(hdrs.len());
+    for hdr in hdrs.iter_mut() {
+        match syscall!(recvmsg(socket, hdr, 0)) {
+            Ok(len) => lens.push(len as usize),
+            // Like `recvmmsg(2)` only return an error if no datagram was
+            // received.
+            Err(err) if lens.is_empty() => return Err(err),
+            Err(_) => break,
+        }
+    }
+    Ok(lens)
+}
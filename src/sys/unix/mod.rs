@@ -25,6 +25,34 @@ cfg_os_poll! {
     mod waker;
     pub(crate) use self::waker::Waker;
 
+    #[cfg(all(feature = "os-util", any(target_os = "android", target_os = "linux")))]
+    mod inotify;
+    #[cfg(all(feature = "os-util", any(target_os = "android", target_os = "linux")))]
+    pub use self::inotify::{Inotify, InotifyEvent, WatchDescriptor, WatchEvent};
+
+    #[cfg(all(feature = "os-util", any(target_os = "android", target_os = "linux")))]
+    mod process;
+    #[cfg(all(feature = "os-util", any(target_os = "android", target_os = "linux")))]
+    pub use self::process::Process;
+
+    #[cfg(all(feature = "os-util", any(target_os = "android", target_os = "linux")))]
+    mod timer;
+    #[cfg(all(feature = "os-util", any(target_os = "android", target_os = "linux")))]
+    pub use self::timer::{Clock, Timer};
+
+    #[cfg(feature = "os-util")]
+    pub mod pipe;
+
+    #[cfg(feature = "os-util")]
+    mod signals;
+    #[cfg(feature = "os-util")]
+    pub use self::signals::{Pending, SignalInfo, Signals};
+
+    #[cfg(feature = "os-util")]
+    mod sigset;
+    #[cfg(feature = "os-util")]
+    pub use self::sigset::SigSet;
+
     cfg_tcp! {
         pub(crate) mod tcp;
     }
@@ -33,6 +61,31 @@ cfg_os_poll! {
         pub(crate) mod udp;
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    cfg_udp! {
+        pub(crate) mod icmp;
+    }
+
+    #[cfg(any(feature = "udp", feature = "uds"))]
+    mod mmsg;
+
+    #[cfg(any(
+        feature = "uds",
+        all(
+            any(feature = "tcp", feature = "udp"),
+            any(target_os = "android", target_os = "linux")
+        )
+    ))]
+    mod cmsg;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg(any(feature = "tcp", feature = "udp"))]
+    pub(crate) mod timestamp;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg(any(feature = "tcp", feature = "udp"))]
+    pub(crate) mod reuseport;
+
     cfg_uds! {
         pub(crate) mod uds;
         pub use self::uds::SocketAddr;
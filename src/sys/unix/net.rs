@@ -5,13 +5,14 @@ use std::net::SocketAddr;
 pub(crate) fn new_ip_socket(
     addr: SocketAddr,
     socket_type: libc::c_int,
+    protocol: libc::c_int,
 ) -> std::io::Result<libc::c_int> {
     let domain = match addr {
         SocketAddr::V4(..) => libc::AF_INET,
         SocketAddr::V6(..) => libc::AF_INET6,
     };
 
-    new_socket(domain, socket_type)
+    new_socket(domain, socket_type, protocol)
 }
 
 /// Create a new non-blocking socket.
@@ -22,6 +23,7 @@ pub(crate) fn new_ip_socket(
 pub(crate) fn new_socket(
     domain: libc::c_int,
     socket_type: libc::c_int,
+    protocol: libc::c_int,
 ) -> std::io::Result<libc::c_int> {
     #[cfg(any(
         target_os = "android",
@@ -36,7 +38,7 @@ pub(crate) fn new_socket(
 
     // Gives a warning for platforms without SOCK_NONBLOCK.
     #[allow(clippy::let_and_return)]
-    let socket = syscall!(socket(domain, socket_type, 0));
+    let socket = syscall!(socket(domain, socket_type, protocol));
 
     // Mimick `libstd` and set `SO_NOSIGPIPE` on apple systems.
     #[cfg(target_vendor = "apple")]
@@ -87,7 +89,7 @@ pub(crate) fn socket_addr(addr: &SocketAddr) -> (*const libc::sockaddr, libc::so
 }
 
 /// `storage` must be initialised to `sockaddr_in` or `sockaddr_in6`.
-#[cfg(all(feature = "os-poll", feature = "tcp"))]
+#[cfg(all(feature = "os-poll", any(feature = "tcp", feature = "udp")))]
 pub(crate) unsafe fn to_socket_addr(
     storage: *const libc::sockaddr_storage,
 ) -> std::io::Result<SocketAddr> {
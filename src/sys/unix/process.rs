@@ -0,0 +1,153 @@
+use crate::sys::unix::SourceFd;
+use crate::{event, Interest, Registry, Token};
+
+use std::fs::File;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, ExitStatus};
+
+/// A process backed by a pidfd (`pidfd_open(2)`), requires Linux 5.3 or
+/// later, and Linux 5.4 or later for [`try_wait`].
+///
+/// `Process` implements [`event::Source`], it becomes readable once the
+/// process exits. After receiving a readable event [`try_wait`] returns the
+/// exit status of the process.
+///
+/// [`event::Source`]: ../event/trait.Source.html
+/// [`try_wait`]: #method.try_wait
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::Process;
+/// use mio::{Events, Interest, Poll, Token};
+/// use std::process::Command;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let child = Command::new("true").spawn()?;
+/// let mut process = Process::from_child(&child)?;
+/// poll.registry().register(&mut process, Token(0), Interest::READABLE)?;
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     if events.iter().any(|event| event.token() == Token(0)) {
+///         let status = process.try_wait()?.expect("process not exited");
+///         assert!(status.success());
+///         break;
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Process {
+    fd: File,
+    pid: libc::pid_t,
+}
+
+impl Process {
+    /// Create a new `Process` for the process with id `pid`.
+    ///
+    /// Any process can be monitored for its exit, but only the exit status of
+    /// child processes can be retrieved using [`try_wait`].
+    ///
+    /// [`try_wait`]: #method.try_wait
+    pub fn new(pid: libc::pid_t) -> io::Result<Process> {
+        // `pidfd_open` always sets the close-on-exec flag.
+        syscall!(syscall(libc::SYS_pidfd_open, pid, 0)).map(|fd| Process {
+            fd: unsafe { File::from_raw_fd(fd as RawFd) },
+            pid,
+        })
+    }
+
+    /// Create a new `Process` for the child process `child`.
+    pub fn from_child(child: &Child) -> io::Result<Process> {
+        Process::new(child.id() as libc::pid_t)
+    }
+
+    /// Returns the process id.
+    pub fn pid(&self) -> libc::pid_t {
+        self.pid
+    }
+
+    /// Returns the exit status of the process, if it has exited, or `None` if
+    /// it's still running.
+    ///
+    /// Just like [`Child::try_wait`] this reaps the child process, this can
+    /// only be done once. This means that after this returns an exit status
+    /// `try_wait` or `wait` on the `Child` will return an error (and vice
+    /// versa).
+    ///
+    /// Returns an error if the process is not a child of the calling process.
+    ///
+    /// [`Child::try_wait`]: std::process::Child::try_wait
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+        // Wait using the pidfd rather than the process id, if the process was
+        // already reaped (e.g. using `Child::wait`) its id could have been
+        // reused by an unrelated process.
+        syscall!(waitid(
+            libc::P_PIDFD,
+            self.fd.as_raw_fd() as libc::id_t,
+            &mut info,
+            libc::WEXITED | libc::WNOHANG,
+        ))?;
+
+        // `si_pid` is left zero if the process hasn't exited yet.
+        if unsafe { info.si_pid() } == 0 {
+            return Ok(None);
+        }
+        // Convert back to the status as returned by `waitpid`, which is what
+        // `ExitStatus` expects.
+        let status = unsafe { info.si_status() };
+        let status = match info.si_code {
+            libc::CLD_EXITED => (status & 0xff) << 8,
+            libc::CLD_DUMPED => status | 0x80,
+            // `CLD_KILLED`, `WEXITED` doesn't report any other codes.
+            _ => status,
+        };
+        Ok(Some(ExitStatus::from_raw(status)))
+    }
+}
+
+impl event::Source for Process {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl AsRawFd for Process {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Process {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
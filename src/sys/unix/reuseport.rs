@@ -0,0 +1,119 @@
+//! `SO_REUSEPORT` groups, optionally steered by the receiving CPU.
+
+use std::io;
+use std::mem::size_of;
+use std::os::unix::io::RawFd;
+
+// Not (yet) defined by libc for all Linux targets, values taken from
+// `asm/socket.h`. Only SPARC differs from the generic values on the
+// architectures Rust supports; PA-RISC (0x402A and 0x402C) has no Rust target
+// and Alpha uses the generic values.
+#[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+const SO_INCOMING_CPU: libc::c_int = 49;
+#[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+const SO_ATTACH_REUSEPORT_CBPF: libc::c_int = 51;
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+const SO_INCOMING_CPU: libc::c_int = 0x0033;
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+const SO_ATTACH_REUSEPORT_CBPF: libc::c_int = 0x0035;
+
+// Classic BPF, see `linux/filter.h` and `linux/bpf_common.h`.
+const BPF_LD: u16 = 0x00;
+const BPF_W: u16 = 0x00;
+const BPF_ABS: u16 = 0x20;
+const BPF_ALU: u16 = 0x04;
+const BPF_MOD: u16 = 0x90;
+const BPF_K: u16 = 0x00;
+const BPF_RET: u16 = 0x06;
+const BPF_A: u16 = 0x10;
+const SKF_AD_OFF: u32 = -0x1000i32 as u32;
+const SKF_AD_CPU: u32 = 36;
+
+#[repr(C)]
+struct SockFilter {
+    code: u16,
+    jt: u8,
+    jf: u8,
+    k: u32,
+}
+
+#[repr(C)]
+struct SockFprog {
+    len: libc::c_ushort,
+    filter: *const SockFilter,
+}
+
+const fn bpf_stmt(code: u16, k: u32) -> SockFilter {
+    SockFilter {
+        code,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
+
+/// Attach a classic BPF program to the reuseport group of `socket` that
+/// selects the socket at index `cpu % size`, where `cpu` is the CPU that
+/// received the packet (or connection) and the index is the order in which
+/// the sockets joined the group.
+pub(crate) fn attach_cpu_steering(socket: RawFd, size: usize) -> io::Result<()> {
+    if size == 0 || size > u32::max_value() as usize {
+        return Err(io::ErrorKind::InvalidInput.into());
+    }
+
+    let code = [
+        // A = current CPU.
+        bpf_stmt(BPF_LD | BPF_W | BPF_ABS, SKF_AD_OFF + SKF_AD_CPU),
+        // A = A % size.
+        bpf_stmt(BPF_ALU | BPF_MOD | BPF_K, size as u32),
+        // Return A as the socket index.
+        bpf_stmt(BPF_RET | BPF_A, 0),
+    ];
+    let program = SockFprog {
+        len: code.len() as libc::c_ushort,
+        filter: code.as_ptr(),
+    };
+
+    set_opt(
+        socket,
+        SO_ATTACH_REUSEPORT_CBPF,
+        &program as *const SockFprog as *const libc::c_void,
+        size_of::<SockFprog>(),
+    )
+}
+
+#[cfg(feature = "udp")]
+pub(crate) fn set_reuseport(socket: RawFd) -> io::Result<()> {
+    set_opt(
+        socket,
+        libc::SO_REUSEPORT,
+        &1 as *const libc::c_int as *const libc::c_void,
+        size_of::<libc::c_int>(),
+    )
+}
+
+pub(crate) fn set_incoming_cpu(socket: RawFd, cpu: usize) -> io::Result<()> {
+    let cpu = cpu as libc::c_int;
+    set_opt(
+        socket,
+        SO_INCOMING_CPU,
+        &cpu as *const libc::c_int as *const libc::c_void,
+        size_of::<libc::c_int>(),
+    )
+}
+
+fn set_opt(
+    socket: RawFd,
+    name: libc::c_int,
+    value: *const libc::c_void,
+    length: usize,
+) -> io::Result<()> {
+    syscall!(setsockopt(
+        socket,
+        libc::SOL_SOCKET,
+        name,
+        value,
+        length as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
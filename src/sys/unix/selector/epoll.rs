@@ -1,10 +1,11 @@
 use crate::{Interest, Token};
 
-use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLRDHUP};
+use libc::{EPOLLET, EPOLLIN, EPOLLONESHOT, EPOLLOUT, EPOLLRDHUP};
 use log::error;
 use std::os::unix::io::{AsRawFd, RawFd};
 #[cfg(debug_assertions)]
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::time::Duration;
 use std::{cmp, i32, io, ptr};
 
@@ -12,27 +13,39 @@ use std::{cmp, i32, io, ptr};
 #[cfg(debug_assertions)]
 static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
 
+/// Whether or not the kernel supports `epoll_pwait2` (Linux 5.11+), set to
+/// false the first time the system call isn't available.
+static HAS_EPOLL_PWAIT2: AtomicBool = AtomicBool::new(true);
+
+/// Data used to register the epoll instance and the timer with the epoll
+/// instance used by `Selector::select_timer`.
+const EP_DATA: u64 = 0;
+const TIMER_DATA: u64 = 1;
+
+const NANOS_PER_MILLI: u32 = 1_000_000;
+
 #[derive(Debug)]
 pub struct Selector {
     #[cfg(debug_assertions)]
     id: usize,
     ep: RawFd,
+    /// timerfd used to support sub-millisecond timeouts on kernels without
+    /// `epoll_pwait2`, lazily created. -1 if not created (yet).
+    timer: AtomicI32,
+    /// epoll instance watching `ep` and `timer`, so that the timer doesn't
+    /// need to be registered with `ep` (using a token the user could also
+    /// use). Created along with `timer`, -1 if not created (yet).
+    timer_ep: AtomicI32,
 }
 
 impl Selector {
     pub fn new() -> io::Result<Selector> {
-        // According to libuv, `EPOLL_CLOEXEC` is not defined on Android API <
-        // 21. But `EPOLL_CLOEXEC` is an alias for `O_CLOEXEC` on that platform,
-        // so we use it instead.
-        #[cfg(target_os = "android")]
-        let flag = libc::O_CLOEXEC;
-        #[cfg(not(target_os = "android"))]
-        let flag = libc::EPOLL_CLOEXEC;
-
-        syscall!(epoll_create1(flag)).map(|ep| Selector {
+        epoll_create().map(|ep| Selector {
             #[cfg(debug_assertions)]
             id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             ep,
+            timer: AtomicI32::new(-1),
+            timer_ep: AtomicI32::new(-1),
         })
     }
 
@@ -42,10 +55,64 @@ impl Selector {
             #[cfg(debug_assertions)]
             id: self.id,
             ep,
+            timer: AtomicI32::new(-1),
+            timer_ep: AtomicI32::new(-1),
         })
     }
 
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        self.select_sigmask(events, timeout, None)
+    }
+
+    #[cfg(feature = "os-util")]
+    pub fn select_with_sigmask(
+        &self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+        sigmask: &libc::sigset_t,
+    ) -> io::Result<()> {
+        self.select_sigmask(events, timeout, Some(sigmask))
+    }
+
+    fn select_sigmask(
+        &self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+        sigmask: Option<&libc::sigset_t>,
+    ) -> io::Result<()> {
+        events.clear();
+        match timeout {
+            // `epoll_wait` only supports millisecond precision.
+            Some(to) if to.subsec_nanos() % NANOS_PER_MILLI != 0 => {
+                // `epoll_pwait2` requires the size of the kernel's signal set,
+                // which differs from `libc::sigset_t`, so we only use it
+                // without a signal mask.
+                if sigmask.is_none() && HAS_EPOLL_PWAIT2.load(Ordering::Relaxed) {
+                    match self.select_pwait2(events, to) {
+                        // Older kernels return `ENOSYS`, but some seccomp
+                        // filters (e.g. older Docker profiles) return `EPERM`
+                        // for unknown system calls.
+                        Err(ref err)
+                            if err.raw_os_error() == Some(libc::ENOSYS)
+                                || err.raw_os_error() == Some(libc::EPERM) =>
+                        {
+                            HAS_EPOLL_PWAIT2.store(false, Ordering::Relaxed);
+                        }
+                        res => return res,
+                    }
+                }
+                self.select_timer(events, to, sigmask)
+            }
+            _ => self.select_millis(events, timeout, sigmask),
+        }
+    }
+
+    fn select_millis(
+        &self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+        sigmask: Option<&libc::sigset_t>,
+    ) -> io::Result<()> {
         // A bug in kernels < 2.6.37 makes timeouts larger than LONG_MAX / CONFIG_HZ
         // (approx. 30 minutes with CONFIG_HZ=1200) effectively infinite on 32 bits
         // architectures. The magic number is the same constant used by libuv.
@@ -58,20 +125,154 @@ impl Selector {
             .map(|to| cmp::min(to.as_millis(), MAX_SAFE_TIMEOUT) as libc::c_int)
             .unwrap_or(-1);
 
-        events.clear();
-        syscall!(epoll_wait(
+        match sigmask {
+            None => syscall!(epoll_wait(
+                self.ep,
+                events.as_mut_ptr(),
+                events.capacity() as i32,
+                timeout,
+            )),
+            Some(sigmask) => epoll_pwait(self.ep, events, timeout, sigmask),
+        }
+        .map(|n_events| {
+            // This is safe because `epoll_(p)wait` ensures that `n_events`
+            // are assigned.
+            unsafe { events.set_len(n_events as usize) };
+        })
+    }
+
+    /// Select using `epoll_pwait2`, which supports nanosecond precision
+    /// timeouts.
+    fn select_pwait2(&self, events: &mut Events, timeout: Duration) -> io::Result<()> {
+        // `epoll_pwait2` uses `__kernel_timespec`, which always has 64 bit
+        // fields, unlike `libc::timespec` on 32 bit architectures.
+        #[repr(C)]
+        struct KernelTimespec {
+            tv_sec: i64,
+            tv_nsec: i64,
+        }
+
+        let timeout = KernelTimespec {
+            tv_sec: cmp::min(timeout.as_secs(), i64::max_value() as u64) as i64,
+            tv_nsec: i64::from(timeout.subsec_nanos()),
+        };
+        syscall!(syscall(
+            libc::SYS_epoll_pwait2,
             self.ep,
             events.as_mut_ptr(),
-            events.capacity() as i32,
-            timeout,
+            events.capacity() as libc::c_int,
+            &timeout as *const KernelTimespec,
+            ptr::null::<libc::sigset_t>(),
+            0 as libc::size_t,
         ))
         .map(|n_events| {
-            // This is safe because `epoll_wait` ensures that `n_events` are
+            // This is safe because `epoll_pwait2` ensures that `n_events` are
             // assigned.
             unsafe { events.set_len(n_events as usize) };
         })
     }
 
+    /// Select using an internal timerfd to support timeouts with nanosecond
+    /// precision on kernels without `epoll_pwait2`.
+    ///
+    /// This waits on a second epoll instance watching both the timer and the
+    /// selector's epoll instance, and then collects the events of the latter.
+    fn select_timer(
+        &self,
+        events: &mut Events,
+        timeout: Duration,
+        sigmask: Option<&libc::sigset_t>,
+    ) -> io::Result<()> {
+        let (timer_ep, timer) = self.timer()?;
+        set_timer(timer, timeout)?;
+        let mut ready = Vec::with_capacity(2);
+        let res = match sigmask {
+            None => syscall!(epoll_wait(timer_ep, ready.as_mut_ptr(), 2, -1)),
+            Some(sigmask) => epoll_pwait(timer_ep, &mut ready, -1, sigmask),
+        };
+        let n_ready = match res {
+            Ok(n_ready) => n_ready as usize,
+            Err(err) => {
+                set_timer(timer, Duration::from_secs(0))?;
+                return Err(err);
+            }
+        };
+        // This is safe because `epoll_(p)wait` ensures that `n_ready` are
+        // assigned.
+        unsafe { ready.set_len(n_ready) };
+
+        // Can't reference fields in packed structures.
+        let fired = ready.iter().any(|event| {
+            let data = event.u64;
+            data == TIMER_DATA
+        });
+        // Disarm the timer if we returned early, otherwise it could wake up a
+        // later call to `select`.
+        if !fired {
+            set_timer(timer, Duration::from_secs(0))?;
+        }
+        let ep_ready = ready.iter().any(|event| {
+            let data = event.u64;
+            data == EP_DATA
+        });
+        if ep_ready {
+            self.select_millis(events, Some(Duration::from_secs(0)), None)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the internal epoll instance and timerfd used by
+    /// `select_timer`, creating them if needed.
+    fn timer(&self) -> io::Result<(RawFd, RawFd)> {
+        let timer_ep = self.timer_ep.load(Ordering::Acquire);
+        if timer_ep != -1 {
+            return Ok((timer_ep, self.timer.load(Ordering::Acquire)));
+        }
+
+        let timer_ep = epoll_create()?;
+        let timer = match syscall!(timerfd_create(
+            libc::CLOCK_MONOTONIC,
+            libc::TFD_NONBLOCK | libc::TFD_CLOEXEC
+        )) {
+            Ok(timer) => timer,
+            Err(err) => {
+                let _ = syscall!(close(timer_ep));
+                return Err(err);
+            }
+        };
+        let mut ep_event = libc::epoll_event {
+            events: EPOLLIN as u32,
+            u64: EP_DATA,
+        };
+        let mut timer_event = libc::epoll_event {
+            events: (EPOLLET | EPOLLIN) as u32,
+            u64: TIMER_DATA,
+        };
+        let res = syscall!(epoll_ctl(
+            timer_ep,
+            libc::EPOLL_CTL_ADD,
+            self.ep,
+            &mut ep_event
+        ))
+        .and_then(|_| {
+            syscall!(epoll_ctl(
+                timer_ep,
+                libc::EPOLL_CTL_ADD,
+                timer,
+                &mut timer_event
+            ))
+        });
+        if let Err(err) = res {
+            let _ = syscall!(close(timer));
+            let _ = syscall!(close(timer_ep));
+            return Err(err);
+        }
+        self.timer.store(timer, Ordering::Release);
+        self.timer_ep.store(timer_ep, Ordering::Release);
+        Ok((timer_ep, timer))
+    }
+
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
         let mut event = libc::epoll_event {
             events: interests_to_epoll(interests),
@@ -115,11 +316,94 @@ impl Drop for Selector {
         if let Err(err) = syscall!(close(self.ep)) {
             error!("error closing epoll: {}", err);
         }
+
+        let timer = *self.timer.get_mut();
+        if timer != -1 {
+            if let Err(err) = syscall!(close(timer)) {
+                error!("error closing timerfd: {}", err);
+            }
+        }
+
+        let timer_ep = *self.timer_ep.get_mut();
+        if timer_ep != -1 {
+            if let Err(err) = syscall!(close(timer_ep)) {
+                error!("error closing epoll: {}", err);
+            }
+        }
     }
 }
 
+/// Creates a new epoll instance.
+fn epoll_create() -> io::Result<RawFd> {
+    // According to libuv, `EPOLL_CLOEXEC` is not defined on Android API <
+    // 21. But `EPOLL_CLOEXEC` is an alias for `O_CLOEXEC` on that platform,
+    // so we use it instead.
+    #[cfg(target_os = "android")]
+    let flag = libc::O_CLOEXEC;
+    #[cfg(not(target_os = "android"))]
+    let flag = libc::EPOLL_CLOEXEC;
+
+    syscall!(epoll_create1(flag))
+}
+
+/// Calls `epoll_pwait`, returning the number of events.
+#[cfg(not(target_os = "android"))]
+fn epoll_pwait(
+    ep: RawFd,
+    events: &mut Events,
+    timeout: libc::c_int,
+    sigmask: &libc::sigset_t,
+) -> io::Result<libc::c_int> {
+    syscall!(epoll_pwait(
+        ep,
+        events.as_mut_ptr(),
+        events.capacity() as i32,
+        timeout,
+        sigmask,
+    ))
+}
+
+/// Calls `epoll_pwait`, returning the number of events.
+#[cfg(target_os = "android")]
+fn epoll_pwait(
+    ep: RawFd,
+    events: &mut Events,
+    timeout: libc::c_int,
+    sigmask: &libc::sigset_t,
+) -> io::Result<libc::c_int> {
+    // Not all Android versions provide `epoll_pwait` in libc, so we make the
+    // system call directly. The kernel's signal set is 64 bits on all
+    // architectures supported by Android.
+    const KERNEL_SIGSET_SIZE: libc::size_t = 8;
+    syscall!(syscall(
+        libc::SYS_epoll_pwait,
+        ep,
+        events.as_mut_ptr(),
+        events.capacity() as libc::c_int,
+        timeout,
+        sigmask as *const libc::sigset_t,
+        KERNEL_SIGSET_SIZE,
+    ))
+    .map(|n_events| n_events as libc::c_int)
+}
+
+/// Arms `timer` to expire once after `timeout`, a zero `timeout` disarms it.
+fn set_timer(timer: RawFd, timeout: Duration) -> io::Result<()> {
+    let spec = libc::itimerspec {
+        it_interval: libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        },
+        it_value: libc::timespec {
+            tv_sec: cmp::min(timeout.as_secs(), libc::time_t::max_value() as u64) as libc::time_t,
+            tv_nsec: libc::c_long::from(timeout.subsec_nanos() as i32),
+        },
+    };
+    syscall!(timerfd_settime(timer, 0, &spec, ptr::null_mut())).map(|_| ())
+}
+
 fn interests_to_epoll(interests: Interest) -> u32 {
-    let mut kind = EPOLLET;
+    let mut kind = if interests.is_level() { 0 } else { EPOLLET };
 
     if interests.is_readable() {
         kind = kind | EPOLLIN | EPOLLRDHUP;
@@ -129,6 +413,10 @@ fn interests_to_epoll(interests: Interest) -> u32 {
         kind |= EPOLLOUT;
     }
 
+    if interests.is_oneshot() {
+        kind |= EPOLLONESHOT;
+    }
+
     kind as u32
 }
 
@@ -233,3 +521,30 @@ fn assert_close_on_exec_flag() {
     // This assertion need to be true for Selector::new.
     assert_eq!(libc::O_CLOEXEC, libc::EPOLL_CLOEXEC);
 }
+
+#[test]
+fn select_timer() {
+    use std::time::Instant;
+
+    let selector = Selector::new().unwrap();
+    let mut events = Vec::with_capacity(8);
+    for _ in 0..2 {
+        let timeout = Duration::from_micros(500);
+        let start = Instant::now();
+        selector.select_timer(&mut events, timeout, None).unwrap();
+        assert!(start.elapsed() >= timeout);
+        // The internal timer's event must not be returned.
+        assert!(events.is_empty());
+    }
+
+    // Events for all tokens are returned, including `usize::MAX`.
+    let fd = syscall!(eventfd(1, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC)).unwrap();
+    let token = Token(usize::max_value());
+    selector.register(fd, token, Interest::READABLE).unwrap();
+    selector
+        .select_timer(&mut events, Duration::from_micros(500), None)
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(event::token(&events[0]), token);
+    syscall!(close(fd)).unwrap();
+}
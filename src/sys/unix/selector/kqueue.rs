@@ -121,8 +121,54 @@ impl Selector {
         })
     }
 
+    /// kqueue doesn't support a signal mask, so we wait for the kqueue to
+    /// become readable using `ppoll`, after which we collect the events
+    /// without blocking.
+    #[cfg(all(feature = "os-util", not(any(target_os = "ios", target_os = "macos"))))]
+    pub fn select_with_sigmask(
+        &self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+        sigmask: &libc::sigset_t,
+    ) -> io::Result<()> {
+        let timeout = timeout.map(|to| libc::timespec {
+            tv_sec: cmp::min(to.as_secs(), libc::time_t::max_value() as u64) as libc::time_t,
+            // See `select` why the cast to i32 is safe.
+            tv_nsec: libc::c_long::from(to.subsec_nanos() as i32),
+        });
+        let timeout = timeout
+            .as_ref()
+            .map(|s| s as *const _)
+            .unwrap_or(ptr::null());
+
+        events.clear();
+        let mut pollfd = libc::pollfd {
+            fd: self.kq,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        match syscall!(ppoll(&mut pollfd, 1, timeout, sigmask))? {
+            0 => Ok(()),
+            _ => self.select(events, Some(Duration::from_secs(0))),
+        }
+    }
+
+    /// macOS doesn't support `ppoll` (and its `pselect` is not atomic).
+    #[cfg(all(feature = "os-util", any(target_os = "ios", target_os = "macos")))]
+    pub fn select_with_sigmask(
+        &self,
+        _: &mut Events,
+        _: Option<Duration>,
+        _: &libc::sigset_t,
+    ) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "polling with a signal mask is not supported on this platform",
+        ))
+    }
+
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
-        let flags = libc::EV_CLEAR | libc::EV_RECEIPT | libc::EV_ADD;
+        let flags = registration_flags(interests) | libc::EV_ADD;
         // At most we need two changes, but maybe we only need 1.
         let mut changes: [MaybeUninit<libc::kevent>; 2] =
             [MaybeUninit::uninit(), MaybeUninit::uninit()];
@@ -161,7 +207,9 @@ impl Selector {
     }
 
     pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
-        let flags = libc::EV_CLEAR | libc::EV_RECEIPT;
+        // Adding an existing (disabled) filter enables it again, this is what
+        // rearms a oneshot registration.
+        let flags = registration_flags(interests);
         let write_flags = if interests.is_writable() {
             flags | libc::EV_ADD
         } else {
@@ -249,6 +297,23 @@ impl Selector {
     }
 }
 
+/// Returns the flags used for a filter registered with `interests`.
+fn registration_flags(interests: Interest) -> Flags {
+    let mut flags = libc::EV_RECEIPT;
+    if !interests.is_level() {
+        // Edge-triggered: reset the state of the filter after the event is
+        // returned.
+        flags |= libc::EV_CLEAR;
+    }
+    if interests.is_oneshot() {
+        // Unlike `EV_ONESHOT`, which deletes the filter after the first event,
+        // `EV_DISPATCH` only disables it. It's enabled again in `reregister`.
+        flags | libc::EV_DISPATCH
+    } else {
+        flags
+    }
+}
+
 /// Register `changes` with `kq`ueue.
 fn kevent_register(
     kq: RawFd,
@@ -0,0 +1,217 @@
+use crate::sys::unix::SourceFd;
+use crate::{event, Interest, Registry, Token};
+
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod signalfd;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use self::signalfd as imp;
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+mod pipe;
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+use self::pipe as imp;
+
+/// Receive Unix signals as readiness events.
+///
+/// `Signals` implements [`event::Source`], it becomes readable once one of the
+/// signals it was created with is received. After receiving a readable event
+/// the received signals can be read using [`receive`] or [`pending`].
+///
+/// On Linux and Android this is backed by `signalfd`. Registering `Signals`
+/// blocks the signals for the calling thread, so they're no longer handled by
+/// their signal handlers (or default action) but queued for the `signalfd`.
+/// On other platforms a signal handler is installed for the signals, which
+/// writes them to a pipe (the self-pipe trick).
+///
+/// Deregistering, or dropping, `Signals` restores the original signal mask
+/// (or signal handlers).
+///
+/// On Linux and Android the signal mask can only be restored on the thread
+/// that registered `Signals`, as `pthread_sigmask(3)` only changes the mask of
+/// the calling thread. Deregistering on another thread returns an error and
+/// dropping on another thread logs one, in both cases the signals remain
+/// blocked on the registering thread.
+///
+/// [`event::Source`]: ../event/trait.Source.html
+/// [`receive`]: #method.receive
+/// [`pending`]: #method.pending
+///
+/// # Notes
+///
+/// The signal mask is per thread. Signals sent to the process, e.g. using
+/// `kill(2)`, are delivered to any of the threads not blocking the signal.
+/// To make sure those signals are received by `Signals`, register it *before*
+/// spawning any threads (as new threads inherit the signal mask) or block the
+/// signals in all threads.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::Signals;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut signals = Signals::new(&[libc::SIGHUP, libc::SIGUSR1])?;
+/// poll.registry().register(&mut signals, Token(0), Interest::READABLE)?;
+///
+/// // Send ourselves a signal.
+/// unsafe { libc::raise(libc::SIGUSR1) };
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     if events.iter().any(|event| event.token() == Token(0)) {
+///         for info in signals.pending() {
+///             let info = info?;
+///             assert_eq!(info.signal(), libc::SIGUSR1);
+///             # return Ok(());
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Signals {
+    inner: imp::Signals,
+}
+
+/// Information about a received signal, returned by [`Signals`].
+///
+/// [`Signals`]: struct.Signals.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SignalInfo {
+    signal: libc::c_int,
+    pid: libc::pid_t,
+    uid: libc::uid_t,
+}
+
+impl SignalInfo {
+    /// The signal number, e.g. `libc::SIGTERM`.
+    pub fn signal(&self) -> libc::c_int {
+        self.signal
+    }
+
+    /// The process id of the sender.
+    ///
+    /// Only meaningful for signals sent by a process, e.g. using `kill(2)`.
+    pub fn pid(&self) -> libc::pid_t {
+        self.pid
+    }
+
+    /// The real user id of the sender.
+    ///
+    /// Only meaningful for signals sent by a process, e.g. using `kill(2)`.
+    pub fn uid(&self) -> libc::uid_t {
+        self.uid
+    }
+}
+
+impl Signals {
+    /// Create a new `Signals` receiving `signals`.
+    ///
+    /// The signals are only blocked (or handled) once `Signals` is registered.
+    ///
+    /// Returns an `InvalidInput` error if `signals` contains an invalid signal
+    /// number, or a signal that can't be caught (`SIGKILL` and `SIGSTOP`).
+    pub fn new(signals: &[libc::c_int]) -> io::Result<Signals> {
+        for &signal in signals {
+            if signal <= 0 || signal == libc::SIGKILL || signal == libc::SIGSTOP {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid signal",
+                ));
+            }
+        }
+        imp::Signals::new(signals).map(|inner| Signals { inner })
+    }
+
+    /// Receive a single pending signal.
+    ///
+    /// Returns `None` if no signal is pending.
+    pub fn receive(&mut self) -> io::Result<Option<SignalInfo>> {
+        self.inner.receive()
+    }
+
+    /// Returns an iterator over all pending signals.
+    ///
+    /// The iterator ends once no more signals are pending, or after it
+    /// returned an error.
+    pub fn pending(&mut self) -> Pending<'_> {
+        Pending {
+            signals: self,
+            done: false,
+        }
+    }
+}
+
+/// Iterator over pending signals, see [`Signals::pending`].
+///
+/// [`Signals::pending`]: struct.Signals.html#method.pending
+#[derive(Debug)]
+pub struct Pending<'a> {
+    signals: &'a mut Signals,
+    done: bool,
+}
+
+impl<'a> Iterator for Pending<'a> {
+    type Item = io::Result<SignalInfo>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.signals.receive().transpose();
+        // Don't keep retrying a persistent error.
+        match result {
+            Some(Ok(_)) => {}
+            _ => self.done = true,
+        }
+        result
+    }
+}
+
+impl event::Source for Signals {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        let enabled = self.inner.enable()?;
+        let res = SourceFd(&self.inner.as_raw_fd()).register(registry, token, interests);
+        // Don't undo a previous registration.
+        if res.is_err() && enabled {
+            let _ = self.inner.disable();
+        }
+        res
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.inner.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.inner.as_raw_fd())
+            .deregister(registry)
+            .and_then(|()| self.inner.disable())
+    }
+}
+
+impl AsRawFd for Signals {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
@@ -0,0 +1,229 @@
+use super::SignalInfo;
+use crate::sys::unix::pipe::new_raw;
+
+use log::error;
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::sync::atomic::{AtomicI32, AtomicPtr, Ordering};
+use std::{fmt, mem, ptr, slice};
+
+/// Maximum supported signal number (exclusive).
+const MAX_SIGNAL: usize = 128;
+
+/// Write end of the pipe of the `Signals` handling a signal, indexed by signal
+/// number, -1 if no `Signals` handles the signal. Lazily allocated, see
+/// `senders`.
+static SENDERS: AtomicPtr<AtomicI32> = AtomicPtr::new(ptr::null_mut());
+
+/// Signal information as written to the pipe by the signal handler.
+#[repr(C)]
+struct RawInfo {
+    signal: libc::c_int,
+    pid: libc::pid_t,
+    uid: libc::uid_t,
+}
+
+/// `Signals` backed by a pipe (the self-pipe trick).
+///
+/// `enable` installs a signal handler for the signals, which writes the
+/// signal information to the pipe.
+pub struct Signals {
+    receiver: File,
+    sender: File,
+    signals: Vec<libc::c_int>,
+    /// Signal handlers replaced by `enable`, empty if not enabled.
+    old_actions: Vec<(libc::c_int, libc::sigaction)>,
+}
+
+impl Signals {
+    pub fn new(signals: &[libc::c_int]) -> io::Result<Signals> {
+        if signals.iter().any(|&signal| signal as usize >= MAX_SIGNAL) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid signal",
+            ));
+        }
+
+        new_raw().map(|fds| Signals {
+            receiver: unsafe { File::from_raw_fd(fds[0]) },
+            sender: unsafe { File::from_raw_fd(fds[1]) },
+            signals: signals.to_vec(),
+            old_actions: Vec::new(),
+        })
+    }
+
+    /// Install the signal handlers, returns false if they were already
+    /// installed.
+    pub fn enable(&mut self) -> io::Result<bool> {
+        if !self.old_actions.is_empty() || self.signals.is_empty() {
+            return Ok(false);
+        }
+
+        let senders = senders();
+        for &signal in &self.signals {
+            senders[signal as usize].store(self.sender.as_raw_fd(), Ordering::Relaxed);
+
+            let mut action: libc::sigaction = unsafe { mem::zeroed() };
+            action.sa_sigaction = handler as libc::sighandler_t;
+            action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+            let mut old_action: libc::sigaction = unsafe { mem::zeroed() };
+            if let Err(err) = syscall!(sigaction(signal, &action, &mut old_action)) {
+                let _ = self.disable();
+                return Err(err);
+            }
+            self.old_actions.push((signal, old_action));
+        }
+        Ok(true)
+    }
+
+    /// Restore the signal handlers replaced by `enable`.
+    pub fn disable(&mut self) -> io::Result<()> {
+        let senders = senders();
+        let sender = self.sender.as_raw_fd();
+        let mut res = Ok(());
+        for (signal, old_action) in self.old_actions.drain(..) {
+            if let Err(err) = syscall!(sigaction(signal, &old_action, ptr::null_mut())) {
+                res = Err(err);
+            }
+            // Only reset the sender if it wasn't replaced by another `Signals`.
+            let _ = senders[signal as usize].compare_exchange(
+                sender,
+                -1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+        }
+        res
+    }
+
+    pub fn receive(&mut self) -> io::Result<Option<SignalInfo>> {
+        let mut info = RawInfo {
+            signal: 0,
+            pid: 0,
+            uid: 0,
+        };
+        let buf = unsafe {
+            slice::from_raw_parts_mut(
+                &mut info as *mut RawInfo as *mut u8,
+                mem::size_of::<RawInfo>(),
+            )
+        };
+        // Writes of less than `PIPE_BUF` bytes are atomic, so we never read
+        // partial information.
+        match (&self.receiver).read_exact(buf) {
+            Ok(()) => Ok(Some(SignalInfo {
+                signal: info.signal,
+                pid: info.pid,
+                uid: info.uid,
+            })),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl AsRawFd for Signals {
+    fn as_raw_fd(&self) -> RawFd {
+        self.receiver.as_raw_fd()
+    }
+}
+
+impl fmt::Debug for Signals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signals")
+            .field("receiver", &self.receiver)
+            .field("sender", &self.sender)
+            .field("signals", &self.signals)
+            .field("enabled", &!self.old_actions.is_empty())
+            .finish()
+    }
+}
+
+impl Drop for Signals {
+    fn drop(&mut self) {
+        if let Err(err) = self.disable() {
+            error!("error restoring signal handlers: {}", err);
+        }
+    }
+}
+
+/// Returns the senders, allocating them if needed.
+fn senders() -> &'static [AtomicI32] {
+    let mut senders = SENDERS.load(Ordering::Acquire);
+    if senders.is_null() {
+        let new: Box<[AtomicI32]> = (0..MAX_SIGNAL).map(|_| AtomicI32::new(-1)).collect();
+        let new = Box::into_raw(new);
+        senders = match SENDERS.compare_exchange(
+            ptr::null_mut(),
+            new as *mut AtomicI32,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => new as *mut AtomicI32,
+            Err(current) => {
+                // Another thread was first.
+                drop(unsafe { Box::from_raw(new) });
+                current
+            }
+        };
+    }
+    unsafe { slice::from_raw_parts(senders, MAX_SIGNAL) }
+}
+
+/// Signal handler writing the signal information to the pipe of the `Signals`
+/// handling `signal`.
+extern "C" fn handler(signal: libc::c_int, info: *mut libc::siginfo_t, _: *mut libc::c_void) {
+    // The signal may interrupt code about to inspect `errno`, which `write`
+    // could overwrite.
+    let errno = unsafe { *errno_location() };
+    send_signal(signal, info);
+    unsafe { *errno_location() = errno };
+}
+
+/// Writes the signal information to the pipe, see `handler`.
+fn send_signal(signal: libc::c_int, info: *mut libc::siginfo_t) {
+    // Only async-signal-safe functions may be used here. The senders are
+    // always allocated before the handler is installed.
+    let senders = SENDERS.load(Ordering::Acquire);
+    if senders.is_null() || signal < 0 || signal as usize >= MAX_SIGNAL {
+        return;
+    }
+    let sender = unsafe { &*senders.add(signal as usize) }.load(Ordering::Relaxed);
+    if sender == -1 {
+        return;
+    }
+
+    let (pid, uid) = unsafe { ((*info).si_pid(), (*info).si_uid()) };
+    let info = RawInfo { signal, pid, uid };
+    // If the pipe is full the signal is dropped, but the `Signals` is
+    // readable so it will still be woken.
+    let _ = unsafe {
+        libc::write(
+            sender,
+            &info as *const RawInfo as *const libc::c_void,
+            mem::size_of::<RawInfo>(),
+        )
+    };
+}
+
+/// Returns a pointer to the thread's `errno`.
+#[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__error()
+}
+
+#[cfg(target_os = "dragonfly")]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__errno_location()
+}
+
+#[cfg(any(target_os = "netbsd", target_os = "openbsd"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__errno()
+}
+
+#[cfg(any(target_os = "illumos", target_os = "solaris"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::___errno()
+}
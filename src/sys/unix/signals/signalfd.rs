@@ -0,0 +1,125 @@
+use super::SignalInfo;
+
+use log::error;
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::thread::{self, ThreadId};
+use std::{mem, ptr, slice};
+
+/// `Signals` backed by `signalfd`.
+///
+/// The signals must be blocked to be queued for the `signalfd`, rather than
+/// being handled by their signal handler, this is done by `enable`.
+#[derive(Debug)]
+pub struct Signals {
+    fd: File,
+    signals: Vec<libc::c_int>,
+    /// Signals blocked by `enable` (i.e. the signals that weren't already
+    /// blocked) and the thread that blocked them, `None` if not enabled.
+    blocked: Option<(ThreadId, Vec<libc::c_int>)>,
+}
+
+impl Signals {
+    pub fn new(signals: &[libc::c_int]) -> io::Result<Signals> {
+        let mask = sigset(signals)?;
+        syscall!(signalfd(-1, &mask, libc::SFD_NONBLOCK | libc::SFD_CLOEXEC)).map(|fd| Signals {
+            fd: unsafe { File::from_raw_fd(fd) },
+            signals: signals.to_vec(),
+            blocked: None,
+        })
+    }
+
+    /// Block the signals for the calling thread, returns false if the
+    /// signals were already blocked by us.
+    pub fn enable(&mut self) -> io::Result<bool> {
+        if self.blocked.is_some() {
+            return Ok(false);
+        }
+
+        let mask = sigset(&self.signals)?;
+        let mut old_mask: libc::sigset_t = unsafe { mem::zeroed() };
+        sigmask(libc::SIG_BLOCK, &mask, &mut old_mask)?;
+        let blocked = self
+            .signals
+            .iter()
+            .cloned()
+            .filter(|&signal| unsafe { libc::sigismember(&old_mask, signal) } == 0)
+            .collect();
+        self.blocked = Some((thread::current().id(), blocked));
+        Ok(true)
+    }
+
+    /// Unblock the signals blocked by `enable`, restoring the original signal
+    /// mask.
+    ///
+    /// The signal mask is per thread, so this must be called on the thread
+    /// that called `enable`. On any other thread the mask is left untouched
+    /// and an error is returned.
+    pub fn disable(&mut self) -> io::Result<()> {
+        match self.blocked.take() {
+            // `io::Error::other` requires Rust 1.74.
+            #[allow(clippy::io_other_error)]
+            Some((thread, _)) if thread != thread::current().id() => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "signal mask can only be restored on the thread that registered `Signals`",
+            )),
+            Some((_, ref blocked)) if !blocked.is_empty() => {
+                let mask = sigset(blocked)?;
+                sigmask(libc::SIG_UNBLOCK, &mask, ptr::null_mut())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn receive(&mut self) -> io::Result<Option<SignalInfo>> {
+        let mut info: libc::signalfd_siginfo = unsafe { mem::zeroed() };
+        let buf = unsafe {
+            slice::from_raw_parts_mut(
+                &mut info as *mut libc::signalfd_siginfo as *mut u8,
+                mem::size_of::<libc::signalfd_siginfo>(),
+            )
+        };
+        match (&self.fd).read_exact(buf) {
+            Ok(()) => Ok(Some(SignalInfo {
+                signal: info.ssi_signo as libc::c_int,
+                pid: info.ssi_pid as libc::pid_t,
+                uid: info.ssi_uid as libc::uid_t,
+            })),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl AsRawFd for Signals {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl Drop for Signals {
+    fn drop(&mut self) {
+        if let Err(err) = self.disable() {
+            error!("error restoring signal mask: {}", err);
+        }
+    }
+}
+
+fn sigset(signals: &[libc::c_int]) -> io::Result<libc::sigset_t> {
+    let mut set: libc::sigset_t = unsafe { mem::zeroed() };
+    syscall!(sigemptyset(&mut set))?;
+    for &signal in signals {
+        syscall!(sigaddset(&mut set, signal))?;
+    }
+    Ok(set)
+}
+
+fn sigmask(how: libc::c_int, set: &libc::sigset_t, old_set: *mut libc::sigset_t) -> io::Result<()> {
+    // `pthread_sigmask` returns the error, rather than setting `errno`.
+    match unsafe { libc::pthread_sigmask(how, set, old_set) } {
+        0 => Ok(()),
+        err => Err(io::Error::from_raw_os_error(err)),
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fmt, io, mem, ptr};
+
+/// A set of signals, used as signal mask.
+///
+/// See [`Poll::poll_with_sigmask`].
+///
+/// [`Poll::poll_with_sigmask`]: ../struct.Poll.html#method.poll_with_sigmask
+#[derive(Copy, Clone)]
+pub struct SigSet {
+    inner: libc::sigset_t,
+}
+
+impl SigSet {
+    /// Create a new set without any signals.
+    pub fn empty() -> SigSet {
+        let mut inner: libc::sigset_t = unsafe { mem::zeroed() };
+        let _ = unsafe { libc::sigemptyset(&mut inner) };
+        SigSet { inner }
+    }
+
+    /// Create a new set with all signals.
+    pub fn full() -> SigSet {
+        let mut inner: libc::sigset_t = unsafe { mem::zeroed() };
+        let _ = unsafe { libc::sigfillset(&mut inner) };
+        SigSet { inner }
+    }
+
+    /// Returns the signal mask of the calling thread.
+    pub fn current() -> io::Result<SigSet> {
+        let mut inner: libc::sigset_t = unsafe { mem::zeroed() };
+        // `pthread_sigmask` returns the error, rather than setting `errno`.
+        match unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, ptr::null(), &mut inner) } {
+            0 => Ok(SigSet { inner }),
+            err => Err(io::Error::from_raw_os_error(err)),
+        }
+    }
+
+    /// Add `signal` to the set.
+    ///
+    /// Returns an `InvalidInput` error if `signal` is not a valid signal.
+    pub fn add(&mut self, signal: libc::c_int) -> io::Result<()> {
+        syscall!(sigaddset(&mut self.inner, signal)).map(|_| ())
+    }
+
+    /// Remove `signal` from the set.
+    ///
+    /// Returns an `InvalidInput` error if `signal` is not a valid signal.
+    pub fn remove(&mut self, signal: libc::c_int) -> io::Result<()> {
+        syscall!(sigdelset(&mut self.inner, signal)).map(|_| ())
+    }
+
+    /// Returns true if the set contains `signal`.
+    pub fn contains(&self, signal: libc::c_int) -> bool {
+        unsafe { libc::sigismember(&self.inner, signal) == 1 }
+    }
+
+    pub(crate) fn as_raw(&self) -> &libc::sigset_t {
+        &self.inner
+    }
+}
+
+impl fmt::Debug for SigSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Signal numbers are small, but the maximum differs per platform.
+        let signals = (1..128).filter(|&signal| self.contains(signal));
+        f.debug_set().entries(signals).finish()
+    }
+}
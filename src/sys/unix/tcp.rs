@@ -1,56 +1,320 @@
-use std::io;
 use std::mem::{size_of, MaybeUninit};
-use std::net::{self, SocketAddr};
+use std::net::{self, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::os::unix::io::{AsRawFd, FromRawFd};
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+use std::time::Duration;
+use std::{cmp, io};
 
 use crate::sys::unix::net::{new_ip_socket, socket_addr, to_socket_addr};
 
-pub fn connect(addr: SocketAddr) -> io::Result<net::TcpStream> {
-    new_ip_socket(addr, libc::SOCK_STREAM)
-        .and_then(|socket| {
-            let (raw_addr, raw_addr_length) = socket_addr(&addr);
-            syscall!(connect(socket, raw_addr, raw_addr_length))
-                .or_else(|err| match err {
-                    // Connect hasn't finished, but that is fine.
-                    ref err if err.raw_os_error() == Some(libc::EINPROGRESS) => Ok(0),
-                    err => Err(err),
-                })
-                .map(|_| socket)
-                .map_err(|err| {
-                    // Close the socket if we hit an error, ignoring the error
-                    // from closing since we can't pass back two errors.
-                    let _ = unsafe { libc::close(socket) };
-                    err
-                })
-        })
-        .map(|socket| unsafe { net::TcpStream::from_raw_fd(socket) })
+pub(crate) type TcpSocket = libc::c_int;
+
+pub(crate) fn new_v4_socket() -> io::Result<TcpSocket> {
+    new_for_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
 }
 
-pub fn bind(addr: SocketAddr) -> io::Result<net::TcpListener> {
-    new_ip_socket(addr, libc::SOCK_STREAM).and_then(|socket| {
-        // Set SO_REUSEADDR (mirrors what libstd does).
-        syscall!(setsockopt(
-            socket,
-            libc::SOL_SOCKET,
-            libc::SO_REUSEADDR,
-            &1 as *const libc::c_int as *const libc::c_void,
-            size_of::<libc::c_int>() as libc::socklen_t,
-        ))
-        .and_then(|_| {
-            let (raw_addr, raw_addr_length) = socket_addr(&addr);
-            syscall!(bind(socket, raw_addr, raw_addr_length))
-        })
-        .and_then(|_| syscall!(listen(socket, 1024)))
-        .map_err(|err| {
-            // Close the socket if we hit an error, ignoring the error
-            // from closing since we can't pass back two errors.
-            let _ = unsafe { libc::close(socket) };
-            err
-        })
-        .map(|_| unsafe { net::TcpListener::from_raw_fd(socket) })
+pub(crate) fn new_v6_socket() -> io::Result<TcpSocket> {
+    new_for_addr(SocketAddr::V6(SocketAddrV6::new(
+        Ipv6Addr::UNSPECIFIED,
+        0,
+        0,
+        0,
+    )))
+}
+
+pub(crate) fn new_for_addr(addr: SocketAddr) -> io::Result<TcpSocket> {
+    new_ip_socket(addr, libc::SOCK_STREAM, 0)
+}
+
+pub(crate) fn bind(socket: TcpSocket, addr: SocketAddr) -> io::Result<()> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(bind(socket, raw_addr, raw_addr_length))?;
+    Ok(())
+}
+
+pub(crate) fn connect(socket: TcpSocket, addr: SocketAddr) -> io::Result<net::TcpStream> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+
+    match syscall!(connect(socket, raw_addr, raw_addr_length)) {
+        // Connect hasn't finished, but that is fine.
+        Err(err) if err.raw_os_error() != Some(libc::EINPROGRESS) => Err(err),
+        _ => Ok(unsafe { net::TcpStream::from_raw_fd(socket) }),
+    }
+}
+
+/// Returns `Ok(true)` if the connection is established and `Ok(false)` while
+/// it's still being established.
+pub(crate) fn connect_status(stream: &net::TcpStream) -> io::Result<bool> {
+    match stream.take_error() {
+        Ok(Some(err)) | Err(err) => return Err(err),
+        Ok(None) => {}
+    }
+
+    match stream.peer_addr() {
+        Ok(_) => return Ok(true),
+        Err(ref err)
+            if err.kind() == io::ErrorKind::NotConnected
+                || err.raw_os_error() == Some(libc::EINPROGRESS) => {}
+        Err(err) => return Err(err),
+    }
+
+    // `getpeername(2)` can't tell a connect that is in progress apart from
+    // one that failed and whose error was already taken, e.g. by a read.
+    // Peeking can: it returns `EWOULDBLOCK` only while connecting.
+    let mut buf = [0u8; 1];
+    match syscall!(recv(
+        stream.as_raw_fd(),
+        buf.as_mut_ptr() as *mut libc::c_void,
+        buf.len(),
+        libc::MSG_PEEK,
+    )) {
+        // Data arrived, so the connection was established after calling
+        // `getpeername`.
+        Ok(n) if n > 0 => Ok(true),
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(false),
+        // A failed connect shuts the socket down, so it returns end of file.
+        // Unless the connection was established and closed again after
+        // calling `getpeername`, which we can check by calling it again.
+        Ok(_) => stream.peer_addr().map(|_| true),
+        Err(err) => Err(err),
+    }
+}
+
+pub(crate) fn listen(socket: TcpSocket, backlog: u32) -> io::Result<net::TcpListener> {
+    let backlog = cmp::min(backlog, libc::c_int::max_value() as u32) as libc::c_int;
+    syscall!(listen(socket, backlog))?;
+    Ok(unsafe { net::TcpListener::from_raw_fd(socket) })
+}
+
+pub(crate) fn close(socket: TcpSocket) {
+    let _ = unsafe { net::TcpStream::from_raw_fd(socket) };
+}
+
+pub(crate) fn set_reuseaddr(socket: TcpSocket, reuseaddr: bool) -> io::Result<()> {
+    set_opt(
+        socket,
+        libc::SOL_SOCKET,
+        libc::SO_REUSEADDR,
+        reuseaddr as libc::c_int,
+    )
+}
+
+pub(crate) fn get_reuseaddr(socket: TcpSocket) -> io::Result<bool> {
+    get_opt(socket, libc::SOL_SOCKET, libc::SO_REUSEADDR).map(|value| value != 0)
+}
+
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_reuseport(socket: TcpSocket, reuseport: bool) -> io::Result<()> {
+    set_opt(
+        socket,
+        libc::SOL_SOCKET,
+        libc::SO_REUSEPORT,
+        reuseport as libc::c_int,
+    )
+}
+
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_reuseport(socket: TcpSocket) -> io::Result<bool> {
+    get_opt(socket, libc::SOL_SOCKET, libc::SO_REUSEPORT).map(|value| value != 0)
+}
+
+pub(crate) fn set_send_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = cmp::min(size, libc::c_int::max_value() as u32) as libc::c_int;
+    set_opt(socket, libc::SOL_SOCKET, libc::SO_SNDBUF, size)
+}
+
+pub(crate) fn get_send_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    get_opt(socket, libc::SOL_SOCKET, libc::SO_SNDBUF).map(|size| size as u32)
+}
+
+pub(crate) fn set_recv_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = cmp::min(size, libc::c_int::max_value() as u32) as libc::c_int;
+    set_opt(socket, libc::SOL_SOCKET, libc::SO_RCVBUF, size)
+}
+
+pub(crate) fn get_recv_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    get_opt(socket, libc::SOL_SOCKET, libc::SO_RCVBUF).map(|size| size as u32)
+}
+
+pub(crate) fn set_keepalive(socket: TcpSocket, keepalive: bool) -> io::Result<()> {
+    set_opt(
+        socket,
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        keepalive as libc::c_int,
+    )
+}
+
+pub(crate) fn get_keepalive(socket: TcpSocket) -> io::Result<bool> {
+    get_opt(socket, libc::SOL_SOCKET, libc::SO_KEEPALIVE).map(|value| value != 0)
+}
+
+// macOS and iOS call `TCP_KEEPIDLE` `TCP_KEEPALIVE`.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+const KEEPALIVE_TIME: libc::c_int = libc::TCP_KEEPALIVE;
+#[cfg(not(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos"
+)))]
+const KEEPALIVE_TIME: libc::c_int = libc::TCP_KEEPIDLE;
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_keepalive_time(socket: TcpSocket, time: Duration) -> io::Result<()> {
+    set_opt(socket, libc::IPPROTO_TCP, KEEPALIVE_TIME, secs(time))
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_keepalive_time(socket: TcpSocket) -> io::Result<Duration> {
+    get_opt(socket, libc::IPPROTO_TCP, KEEPALIVE_TIME).map(|secs| Duration::from_secs(secs as u64))
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_keepalive_interval(socket: TcpSocket, interval: Duration) -> io::Result<()> {
+    set_opt(
+        socket,
+        libc::IPPROTO_TCP,
+        libc::TCP_KEEPINTVL,
+        secs(interval),
+    )
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_keepalive_interval(socket: TcpSocket) -> io::Result<Duration> {
+    get_opt(socket, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL)
+        .map(|secs| Duration::from_secs(secs as u64))
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_keepalive_retries(socket: TcpSocket, retries: u32) -> io::Result<()> {
+    let retries = cmp::min(retries, libc::c_int::max_value() as u32) as libc::c_int;
+    set_opt(socket, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, retries)
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_keepalive_retries(socket: TcpSocket) -> io::Result<u32> {
+    get_opt(socket, libc::IPPROTO_TCP, libc::TCP_KEEPCNT).map(|retries| retries as u32)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_user_timeout(socket: TcpSocket, timeout: Option<Duration>) -> io::Result<()> {
+    // Zero means the system default is used.
+    let timeout = timeout.map_or(0, |timeout| {
+        cmp::min(timeout.as_millis(), libc::c_int::max_value() as u128) as libc::c_int
+    });
+    set_opt(socket, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT, timeout)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn get_user_timeout(socket: TcpSocket) -> io::Result<Option<Duration>> {
+    get_opt(socket, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT).map(|timeout| {
+        if timeout == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(timeout as u64))
+        }
     })
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_fastopen(socket: TcpSocket, queue_len: u32) -> io::Result<()> {
+    let queue_len = cmp::min(queue_len, libc::c_int::max_value() as u32) as libc::c_int;
+    set_opt(socket, libc::IPPROTO_TCP, libc::TCP_FASTOPEN, queue_len)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn get_fastopen(socket: TcpSocket) -> io::Result<u32> {
+    get_opt(socket, libc::IPPROTO_TCP, libc::TCP_FASTOPEN).map(|queue_len| queue_len as u32)
+}
+
+/// Connects `socket` to `addr` using TCP Fast Open, sending (part of) `data`
+/// along with the SYN. Returns the number of bytes of `data` sent.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn connect_with_data(
+    socket: TcpSocket,
+    addr: SocketAddr,
+    data: &[u8],
+) -> io::Result<(net::TcpStream, usize)> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+
+    match syscall!(sendto(
+        socket,
+        data.as_ptr() as *const libc::c_void,
+        data.len(),
+        libc::MSG_FASTOPEN | libc::MSG_NOSIGNAL,
+        raw_addr,
+        raw_addr_length,
+    )) {
+        Ok(n) => Ok((unsafe { net::TcpStream::from_raw_fd(socket) }, n as usize)),
+        // There is no Fast Open cookie for `addr` yet, so the kernel sent a
+        // regular SYN requesting one, without any data.
+        Err(ref err) if err.raw_os_error() == Some(libc::EINPROGRESS) => {
+            Ok((unsafe { net::TcpStream::from_raw_fd(socket) }, 0))
+        }
+        // Fast Open is disabled for clients (`EOPNOTSUPP`) or not supported
+        // by the kernel at all (`EPIPE`, as the socket isn't connected), so
+        // fall back to a regular connect.
+        Err(ref err)
+            if err.raw_os_error() == Some(libc::EOPNOTSUPP)
+                || err.raw_os_error() == Some(libc::EPIPE) =>
+        {
+            connect(socket, addr).map(|stream| (stream, 0))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Converts `duration` to whole seconds for the keepalive options.
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+fn secs(duration: Duration) -> libc::c_int {
+    cmp::min(duration.as_secs(), libc::c_int::max_value() as u64) as libc::c_int
+}
+
+pub(crate) fn get_localaddr(socket: TcpSocket) -> io::Result<SocketAddr> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+    syscall!(getsockname(
+        socket,
+        addr.as_mut_ptr() as *mut _,
+        &mut length
+    ))?;
+
+    // This is safe because `getsockname` initialised the address.
+    unsafe { to_socket_addr(addr.as_ptr()) }
+}
+
+fn set_opt(
+    socket: TcpSocket,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: libc::c_int,
+) -> io::Result<()> {
+    syscall!(setsockopt(
+        socket,
+        level,
+        name,
+        &value as *const libc::c_int as *const libc::c_void,
+        size_of::<libc::c_int>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+fn get_opt(socket: TcpSocket, level: libc::c_int, name: libc::c_int) -> io::Result<libc::c_int> {
+    let mut value: libc::c_int = 0;
+    let mut length = size_of::<libc::c_int>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket,
+        level,
+        name,
+        &mut value as *mut libc::c_int as *mut libc::c_void,
+        &mut length,
+    ))
+    .map(|_| value)
+}
+
 pub fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
     let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
@@ -0,0 +1,256 @@
+use crate::sys::unix::SourceFd;
+use crate::{event, Interest, Registry, Token};
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::time::Duration;
+use std::{cmp, mem, ptr};
+
+/// Clock used by a [`Timer`] to mark the progress of time.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Clock {
+    /// A settable system-wide real-time clock (`CLOCK_REALTIME`).
+    ///
+    /// Absolute deadlines are expressed as the time since the Unix epoch.
+    Realtime,
+    /// A non-settable monotonically increasing clock (`CLOCK_MONOTONIC`).
+    ///
+    /// This clock does not advance while the system is suspended.
+    Monotonic,
+    /// Like [`Clock::Monotonic`], but also includes any time the system is
+    /// suspended (`CLOCK_BOOTTIME`).
+    Boottime,
+}
+
+impl Clock {
+    fn as_raw(self) -> libc::clockid_t {
+        match self {
+            Clock::Realtime => libc::CLOCK_REALTIME,
+            Clock::Monotonic => libc::CLOCK_MONOTONIC,
+            Clock::Boottime => libc::CLOCK_BOOTTIME,
+        }
+    }
+}
+
+/// A timer backed by `timerfd`.
+///
+/// `Timer` implements [`event::Source`], it becomes readable once it expires.
+/// After receiving a readable event [`read`] returns the number of times the
+/// timer expired, which resets the timer's readiness.
+///
+/// A newly created timer is disarmed, use one of the `set_*` methods to arm
+/// it. Arming an armed timer replaces its previous setting.
+///
+/// [`event::Source`]: ../event/trait.Source.html
+/// [`read`]: #method.read
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::{Clock, Timer};
+/// use mio::{Events, Interest, Poll, Token};
+/// use std::time::Duration;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut timer = Timer::new(Clock::Monotonic)?;
+/// poll.registry().register(&mut timer, Token(0), Interest::READABLE)?;
+///
+/// timer.set_timeout(Duration::from_millis(10))?;
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     if events.iter().any(|event| event.token() == Token(0)) {
+///         assert_eq!(timer.read()?, 1);
+///         break;
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Timer {
+    fd: File,
+}
+
+impl Timer {
+    /// Create a new disarmed timer using `clock`.
+    pub fn new(clock: Clock) -> io::Result<Timer> {
+        syscall!(timerfd_create(
+            clock.as_raw(),
+            libc::TFD_NONBLOCK | libc::TFD_CLOEXEC
+        ))
+        .map(|fd| Timer {
+            fd: unsafe { File::from_raw_fd(fd) },
+        })
+    }
+
+    /// Arm the timer to expire once, after `timeout` has elapsed.
+    ///
+    /// A `timeout` of zero expires the timer (almost) immediately.
+    pub fn set_timeout(&self, timeout: Duration) -> io::Result<()> {
+        self.set(0, non_zero(timeout), Duration::from_secs(0))
+    }
+
+    /// Arm the timer to expire once, when the clock reaches `deadline`.
+    ///
+    /// The `deadline` is absolute, e.g. for [`Clock::Realtime`] it is the
+    /// time since the Unix epoch. A `deadline` in the past expires the timer
+    /// immediately.
+    pub fn set_deadline(&self, deadline: Duration) -> io::Result<()> {
+        self.set(
+            libc::TFD_TIMER_ABSTIME,
+            non_zero(deadline),
+            Duration::from_secs(0),
+        )
+    }
+
+    /// Arm the timer to expire periodically, first after `interval` has
+    /// elapsed and then every `interval` after that.
+    ///
+    /// Returns an `InvalidInput` error if `interval` is zero.
+    pub fn set_interval(&self, interval: Duration) -> io::Result<()> {
+        check_interval(interval)?;
+        self.set(0, interval, interval)
+    }
+
+    /// Arm the timer to expire periodically, first when the clock reaches
+    /// `start` and then every `interval` after that.
+    ///
+    /// Like [`set_deadline`], `start` is absolute.
+    ///
+    /// Returns an `InvalidInput` error if `interval` is zero.
+    ///
+    /// [`set_deadline`]: #method.set_deadline
+    pub fn set_interval_at(&self, start: Duration, interval: Duration) -> io::Result<()> {
+        check_interval(interval)?;
+        self.set(libc::TFD_TIMER_ABSTIME, non_zero(start), interval)
+    }
+
+    /// Disarm the timer.
+    ///
+    /// Expirations that happened before the timer was disarmed can still be
+    /// [`read`].
+    ///
+    /// [`read`]: #method.read
+    pub fn disarm(&self) -> io::Result<()> {
+        self.set(0, Duration::from_secs(0), Duration::from_secs(0))
+    }
+
+    /// Returns the time until the next expiration, or `None` if the timer is
+    /// disarmed.
+    pub fn remaining(&self) -> io::Result<Option<Duration>> {
+        let mut current: libc::itimerspec = unsafe { mem::zeroed() };
+        syscall!(timerfd_gettime(self.fd.as_raw_fd(), &mut current)).map(|_| {
+            let value = current.it_value;
+            if value.tv_sec == 0 && value.tv_nsec == 0 {
+                None
+            } else {
+                Some(Duration::new(value.tv_sec as u64, value.tv_nsec as u32))
+            }
+        })
+    }
+
+    /// Read the number of times the timer expired since it was last read (or
+    /// armed).
+    ///
+    /// Returns a `WouldBlock` error if the timer hasn't expired.
+    pub fn read(&self) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        (&self.fd)
+            .read_exact(&mut buf)
+            .map(|()| u64::from_ne_bytes(buf))
+    }
+
+    fn set(&self, flags: libc::c_int, value: Duration, interval: Duration) -> io::Result<()> {
+        let new = libc::itimerspec {
+            it_interval: to_timespec(interval),
+            it_value: to_timespec(value),
+        };
+        syscall!(timerfd_settime(
+            self.fd.as_raw_fd(),
+            flags,
+            &new,
+            ptr::null_mut()
+        ))
+        .map(|_| ())
+    }
+}
+
+/// A zeroed `it_value` disarms the timer, so we use the smallest non-zero
+/// value instead.
+fn non_zero(duration: Duration) -> Duration {
+    cmp::max(duration, Duration::from_nanos(1))
+}
+
+fn check_interval(interval: Duration) -> io::Result<()> {
+    if interval == Duration::from_secs(0) {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "timer interval must be non-zero",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn to_timespec(duration: Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: cmp::min(duration.as_secs(), libc::time_t::max_value() as u64) as libc::time_t,
+        tv_nsec: duration.subsec_nanos() as libc::c_long,
+    }
+}
+
+impl event::Source for Timer {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.fd.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl AsRawFd for Timer {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Timer {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
+
+impl FromRawFd for Timer {
+    /// Converts a `RawFd` to a `Timer`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the fd is a `timerfd` in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> Timer {
+        Timer {
+            fd: File::from_raw_fd(fd),
+        }
+    }
+}
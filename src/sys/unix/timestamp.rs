@@ -0,0 +1,178 @@
+//! Kernel timestamping, see `Documentation/networking/timestamping.rst` in
+//! the Linux source.
+
+use crate::net::TxTimestamp;
+use crate::sys::unix::cmsg;
+
+use std::io;
+use std::mem::{self, size_of, MaybeUninit};
+use std::os::unix::io::RawFd;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Not (yet) defined by libc for all Linux targets, values taken from
+// `asm/socket.h` and `linux/net_tstamp.h`.
+#[cfg(not(target_arch = "sparc64"))]
+const SO_TIMESTAMPNS: libc::c_int = 35;
+#[cfg(not(target_arch = "sparc64"))]
+const SO_TIMESTAMPING: libc::c_int = 37;
+#[cfg(target_arch = "sparc64")]
+const SO_TIMESTAMPNS: libc::c_int = 0x0021;
+#[cfg(target_arch = "sparc64")]
+const SO_TIMESTAMPING: libc::c_int = 0x0023;
+const SCM_TIMESTAMPNS: libc::c_int = SO_TIMESTAMPNS;
+const SCM_TIMESTAMPING: libc::c_int = SO_TIMESTAMPING;
+
+const SOF_TIMESTAMPING_TX_SOFTWARE: libc::c_int = 1 << 1;
+const SOF_TIMESTAMPING_SOFTWARE: libc::c_int = 1 << 4;
+const SOF_TIMESTAMPING_OPT_ID: libc::c_int = 1 << 7;
+const SOF_TIMESTAMPING_OPT_TSONLY: libc::c_int = 1 << 11;
+const TX_FLAGS: libc::c_int = SOF_TIMESTAMPING_TX_SOFTWARE
+    | SOF_TIMESTAMPING_SOFTWARE
+    | SOF_TIMESTAMPING_OPT_ID
+    | SOF_TIMESTAMPING_OPT_TSONLY;
+
+const SO_EE_ORIGIN_TIMESTAMPING: u8 = 4;
+
+/// `struct sock_extended_err`, see `ip(7)`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct SockExtendedErr {
+    ee_errno: u32,
+    ee_origin: u8,
+    ee_type: u8,
+    ee_code: u8,
+    ee_pad: u8,
+    ee_info: u32,
+    ee_data: u32,
+}
+
+/// Room for an `SCM_TIMESTAMPING` message (three `timespec`s) and an
+/// `IP_RECVERR` or `IPV6_RECVERR` message (a `sock_extended_err` followed by
+/// the offending address).
+const CONTROL_LEN: usize = 128;
+
+fn set_opt(fd: RawFd, name: libc::c_int, value: libc::c_int) -> io::Result<()> {
+    syscall!(setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        name,
+        &value as *const libc::c_int as *const libc::c_void,
+        size_of::<libc::c_int>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+fn get_opt(fd: RawFd, name: libc::c_int) -> io::Result<libc::c_int> {
+    let mut value: libc::c_int = 0;
+    let mut len = size_of::<libc::c_int>() as libc::socklen_t;
+    syscall!(getsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        name,
+        &mut value as *mut libc::c_int as *mut libc::c_void,
+        &mut len,
+    ))
+    .map(|_| value)
+}
+
+pub(crate) fn set_recv_timestamps(fd: RawFd, on: bool) -> io::Result<()> {
+    set_opt(fd, SO_TIMESTAMPNS, on as libc::c_int)
+}
+
+pub(crate) fn recv_timestamps(fd: RawFd) -> io::Result<bool> {
+    get_opt(fd, SO_TIMESTAMPNS).map(|value| value != 0)
+}
+
+pub(crate) fn set_tx_timestamps(fd: RawFd, on: bool) -> io::Result<()> {
+    set_opt(fd, SO_TIMESTAMPING, if on { TX_FLAGS } else { 0 })
+}
+
+pub(crate) fn tx_timestamps(fd: RawFd) -> io::Result<bool> {
+    get_opt(fd, SO_TIMESTAMPING).map(|value| value & SOF_TIMESTAMPING_TX_SOFTWARE != 0)
+}
+
+fn to_system_time(ts: libc::timespec) -> Option<SystemTime> {
+    if ts.tv_sec == 0 && ts.tv_nsec == 0 {
+        // The kernel leaves unused timestamps zeroed.
+        None
+    } else {
+        Some(UNIX_EPOCH + Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+    }
+}
+
+/// Returns the software timestamp from an `SCM_TIMESTAMPNS` or
+/// `SCM_TIMESTAMPING` control message.
+fn timestamp(level: libc::c_int, ty: libc::c_int, data: &[u8]) -> Option<SystemTime> {
+    match (level, ty) {
+        (libc::SOL_SOCKET, SCM_TIMESTAMPNS) | (libc::SOL_SOCKET, SCM_TIMESTAMPING) => {
+            // For `SCM_TIMESTAMPING` the software timestamp comes first.
+            cmsg::read::<libc::timespec>(data).and_then(to_system_time)
+        }
+        _ => None,
+    }
+}
+
+/// Receives data into `buf`, returning the number of bytes read, the address
+/// of the sender and the time the kernel received the data, if timestamps are
+/// enabled.
+pub(crate) fn recv_timestamped(
+    fd: RawFd,
+    buf: &mut [u8],
+) -> io::Result<(usize, libc::sockaddr_storage, Option<SystemTime>)> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::zeroed();
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = addr.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    let mut control = cmsg::Buffer::with_capacity(CONTROL_LEN);
+    control.apply(&mut msg);
+
+    let n = syscall!(recvmsg(fd, &mut msg, 0))?;
+    let time = unsafe { cmsg::messages(&msg) }
+        .filter_map(|(level, ty, data)| timestamp(level, ty, data))
+        .next();
+    // This is safe because the address was zeroed and `recvmsg` doesn't
+    // write more than `sockaddr_storage`.
+    Ok((n as usize, unsafe { addr.assume_init() }, time))
+}
+
+/// Reads a transmit timestamp from the error queue of `fd`.
+pub(crate) fn recv_tx_timestamp(fd: RawFd) -> io::Result<TxTimestamp> {
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    let mut control = cmsg::Buffer::with_capacity(CONTROL_LEN);
+    control.apply(&mut msg);
+
+    syscall!(recvmsg(fd, &mut msg, libc::MSG_ERRQUEUE))?;
+    let mut time = None;
+    let mut id = None;
+    for (level, ty, data) in unsafe { cmsg::messages(&msg) } {
+        match (level, ty) {
+            (libc::SOL_IP, libc::IP_RECVERR) | (libc::SOL_IPV6, libc::IPV6_RECVERR) => {
+                if let Some(err) = cmsg::read::<SockExtendedErr>(data) {
+                    if err.ee_errno == libc::ENOMSG as u32
+                        && err.ee_origin == SO_EE_ORIGIN_TIMESTAMPING
+                    {
+                        id = Some(err.ee_data);
+                    }
+                }
+            }
+            _ => {
+                if let Some(t) = timestamp(level, ty, data) {
+                    time = Some(t);
+                }
+            }
+        }
+    }
+    match (time, id) {
+        (Some(time), Some(id)) => Ok(TxTimestamp::new(time, id)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "error queue message is not a transmit timestamp",
+        )),
+    }
+}
@@ -1,13 +1,25 @@
-use crate::sys::unix::net::{new_ip_socket, socket_addr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::PacketInfo;
+use crate::net::{RecvMsg, SendMsg};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::cmsg;
+use crate::sys::unix::mmsg;
+use crate::sys::unix::net::{new_ip_socket, socket_addr, to_socket_addr};
 
-use std::io;
+use std::io::{self, IoSlice, IoSliceMut};
+use std::mem::{self, size_of, MaybeUninit};
 use std::net::{self, SocketAddr};
-use std::os::unix::io::FromRawFd;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::ptr;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::SystemTime;
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     // Gives a warning for non Apple platforms.
     #[allow(clippy::let_and_return)]
-    let socket = new_ip_socket(addr, libc::SOCK_DGRAM);
+    let socket = new_ip_socket(addr, libc::SOCK_DGRAM, 0);
 
     socket.and_then(|socket| {
         let (raw_addr, raw_addr_length) = socket_addr(&addr);
@@ -21,3 +33,370 @@ pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
             .map(|_| unsafe { net::UdpSocket::from_raw_fd(socket) })
     })
 }
+
+/// Create a UDP socket with `SO_REUSEPORT` (and optionally `SO_INCOMING_CPU`)
+/// set and bind it to `addr`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn bind_reuseport(addr: SocketAddr, cpu: Option<usize>) -> io::Result<net::UdpSocket> {
+    use crate::sys::unix::reuseport::{set_incoming_cpu, set_reuseport};
+
+    // Ensure the socket is closed if any of the calls below fail.
+    let socket = new_ip_socket(addr, libc::SOCK_DGRAM, 0)
+        .map(|socket| unsafe { net::UdpSocket::from_raw_fd(socket) })?;
+
+    set_reuseport(socket.as_raw_fd())?;
+    if let Some(cpu) = cpu {
+        set_incoming_cpu(socket.as_raw_fd(), cpu)?;
+    }
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(bind(socket.as_raw_fd(), raw_addr, raw_addr_length))?;
+    Ok(socket)
+}
+
+/// Sends the data in `bufs` to `target`, or to the connected address if
+/// `target` is `None`.
+pub(crate) fn send_vectored(
+    socket: &net::UdpSocket,
+    bufs: &[IoSlice<'_>],
+    target: Option<&SocketAddr>,
+) -> io::Result<usize> {
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    if let Some(target) = target {
+        let (raw_addr, raw_addr_length) = socket_addr(target);
+        msg.msg_name = raw_addr as *mut libc::c_void;
+        msg.msg_namelen = raw_addr_length;
+    }
+    // `IoSlice` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len() as _;
+
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, 0)).map(|n| n as usize)
+}
+
+/// Receives a datagram into `bufs`, returning the number of bytes read and
+/// the address of the sender.
+pub(crate) fn recv_vectored(
+    socket: &net::UdpSocket,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = addr.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    // `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len() as _;
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // This is safe because `recvmsg` initialised the address.
+    unsafe { to_socket_addr(addr.as_ptr()) }.map(|addr| (n as usize, addr))
+}
+
+/// Receives a datagram into `bufs` from the connected address, returning the
+/// number of bytes read.
+pub(crate) fn recv_vectored_connected(
+    socket: &net::UdpSocket,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<usize> {
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    // `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len() as _;
+
+    syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0)).map(|n| n as usize)
+}
+
+pub(crate) fn send_batch(
+    socket: &net::UdpSocket,
+    msgs: &[SendMsg<'_, SocketAddr>],
+) -> io::Result<usize> {
+    mmsg::send_batch(socket.as_raw_fd(), msgs, |addr| {
+        let (raw_addr, raw_addr_length) = socket_addr(addr);
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        unsafe {
+            ptr::copy_nonoverlapping(
+                raw_addr as *const u8,
+                &mut storage as *mut libc::sockaddr_storage as *mut u8,
+                raw_addr_length as usize,
+            )
+        };
+        Ok((storage, raw_addr_length))
+    })
+}
+
+pub(crate) fn recv_batch(
+    socket: &net::UdpSocket,
+    msgs: &mut [RecvMsg<'_, SocketAddr>],
+) -> io::Result<usize> {
+    // This is safe because `recvmmsg` initialised the address.
+    mmsg::recv_batch(socket.as_raw_fd(), msgs, |storage, _| unsafe {
+        to_socket_addr(storage)
+    })
+}
+
+// Not (yet) exported by all versions of the libc crate.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const SOL_UDP: libc::c_int = 17;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const UDP_SEGMENT: libc::c_int = 103;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const UDP_GRO: libc::c_int = 104;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_bool_opt(
+    socket: &net::UdpSocket,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: bool,
+) -> io::Result<()> {
+    let val: libc::c_int = value as libc::c_int;
+    syscall!(setsockopt(
+        socket.as_raw_fd(),
+        level,
+        name,
+        &val as *const libc::c_int as *const libc::c_void,
+        size_of::<libc::c_int>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn get_bool_opt(
+    socket: &net::UdpSocket,
+    level: libc::c_int,
+    name: libc::c_int,
+) -> io::Result<bool> {
+    let mut val: libc::c_int = 0;
+    let mut len = size_of::<libc::c_int>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        level,
+        name,
+        &mut val as *mut libc::c_int as *mut libc::c_void,
+        &mut len,
+    ))
+    .map(|_| val != 0)
+}
+
+/// Sends `buf` along with the control messages in `control`, to `target` or
+/// the connected address if `target` is `None`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send_with_control(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    target: Option<&SocketAddr>,
+    mut control: cmsg::Buffer,
+) -> io::Result<usize> {
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    if let Some(target) = target {
+        let (raw_addr, raw_addr_length) = socket_addr(target);
+        msg.msg_name = raw_addr as *mut libc::c_void;
+        msg.msg_namelen = raw_addr_length;
+    }
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    control.apply(&mut msg);
+
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, 0)).map(|n| n as usize)
+}
+
+/// Receives a datagram into `buf` along with up to `control_len` bytes of
+/// control messages, which are passed to `parse`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_with_control<T, F>(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+    control_len: usize,
+    parse: F,
+) -> io::Result<(usize, SocketAddr, T)>
+where
+    F: FnOnce(cmsg::Messages<'_>) -> T,
+{
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = addr.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    let mut control = cmsg::Buffer::with_capacity(control_len);
+    control.apply(&mut msg);
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    let parsed = parse(unsafe { cmsg::messages(&msg) });
+    // This is safe because `recvmsg` initialised the address.
+    unsafe { to_socket_addr(addr.as_ptr()) }.map(|addr| (n as usize, addr, parsed))
+}
+
+/// Sends `buf` as a train of datagrams of `segment_size` bytes each using
+/// `UDP_SEGMENT`, to `target` or the connected address if `target` is `None`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_segmented(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    segment_size: u16,
+    target: Option<&SocketAddr>,
+) -> io::Result<usize> {
+    let mut control = cmsg::Buffer::new();
+    control.push_value(SOL_UDP, UDP_SEGMENT, segment_size);
+    send_with_control(socket, buf, target, control)
+}
+
+/// Enables or disables `UDP_GRO` on `socket`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_gro(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    set_bool_opt(socket, SOL_UDP, UDP_GRO, enable)
+}
+
+/// Receives a (possibly coalesced) datagram into `buf`, returning the number
+/// of bytes read, the address of the sender and the `UDP_GRO` segment size,
+/// if any.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_gro(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<u16>)> {
+    let control_len = cmsg::space(size_of::<libc::c_int>());
+    recv_with_control(socket, buf, control_len, |mut messages| {
+        messages
+            .find(|&(level, ty, _)| level == SOL_UDP && ty == UDP_GRO)
+            .and_then(|(_, _, data)| cmsg::read::<libc::c_int>(data))
+            .map(|size| size as u16)
+    })
+}
+
+/// Probes whether the kernel supports `UDP_SEGMENT` and `UDP_GRO`, by
+/// setting them on a throwaway socket.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn probe_offload() -> (bool, bool) {
+    let socket = match syscall!(socket(
+        libc::AF_INET,
+        libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+        0
+    )) {
+        Ok(socket) => unsafe { net::UdpSocket::from_raw_fd(socket) },
+        Err(_) => return (false, false),
+    };
+    let gso = syscall!(setsockopt(
+        socket.as_raw_fd(),
+        SOL_UDP,
+        UDP_SEGMENT,
+        &0 as *const libc::c_int as *const libc::c_void,
+        size_of::<libc::c_int>() as libc::socklen_t,
+    ))
+    .is_ok();
+    let gro = set_gro(&socket, true).is_ok();
+    (gso, gro)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_pktinfo_v4(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    set_bool_opt(socket, libc::IPPROTO_IP, libc::IP_PKTINFO, enable)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn pktinfo_v4(socket: &net::UdpSocket) -> io::Result<bool> {
+    get_bool_opt(socket, libc::IPPROTO_IP, libc::IP_PKTINFO)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_pktinfo_v6(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    set_bool_opt(socket, libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO, enable)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo_v6(socket: &net::UdpSocket) -> io::Result<bool> {
+    get_bool_opt(socket, libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO)
+}
+
+/// Sends `buf` to `target` with the source address and outgoing interface
+/// in `info`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_pktinfo(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    target: &SocketAddr,
+    info: &PacketInfo,
+) -> io::Result<usize> {
+    let mut control = cmsg::Buffer::new();
+    match info.addr() {
+        IpAddr::V4(addr) => control.push_value(
+            libc::IPPROTO_IP,
+            libc::IP_PKTINFO,
+            libc::in_pktinfo {
+                ipi_ifindex: info.interface() as libc::c_int,
+                ipi_spec_dst: libc::in_addr {
+                    s_addr: u32::from_ne_bytes(addr.octets()),
+                },
+                ipi_addr: libc::in_addr { s_addr: 0 },
+            },
+        ),
+        IpAddr::V6(addr) => control.push_value(
+            libc::IPPROTO_IPV6,
+            libc::IPV6_PKTINFO,
+            libc::in6_pktinfo {
+                ipi6_addr: libc::in6_addr {
+                    s6_addr: addr.octets(),
+                },
+                // `c_int` on Android.
+                ipi6_ifindex: info.interface() as _,
+            },
+        ),
+    }
+    send_with_control(socket, buf, Some(target), control)
+}
+
+/// Receives a datagram into `buf`, returning the number of bytes read, the
+/// address of the sender and the destination address and interface the
+/// datagram arrived on, if packet info is enabled.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<PacketInfo>)> {
+    let control_len =
+        cmsg::space(size_of::<libc::in_pktinfo>()) + cmsg::space(size_of::<libc::in6_pktinfo>());
+    recv_with_control(socket, buf, control_len, |messages| {
+        messages
+            .filter_map(|(level, ty, data)| match (level, ty) {
+                (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                    cmsg::read::<libc::in_pktinfo>(data).map(|info| {
+                        let addr = Ipv4Addr::from(info.ipi_addr.s_addr.to_ne_bytes());
+                        PacketInfo::new(IpAddr::V4(addr), info.ipi_ifindex as u32)
+                    })
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => cmsg::read::<libc::in6_pktinfo>(data)
+                    .map(|info| {
+                        let addr = Ipv6Addr::from(info.ipi6_addr.s6_addr);
+                        // `c_int` on Android.
+                        #[allow(clippy::unnecessary_cast)]
+                        let interface = info.ipi6_ifindex as u32;
+                        PacketInfo::new(IpAddr::V6(addr), interface)
+                    }),
+                _ => None,
+            })
+            .next()
+    })
+}
+
+/// Receives a datagram into `buf`, returning the number of bytes read, the
+/// address of the sender and the time the kernel received it, if timestamps
+/// are enabled.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_timestamped(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<SystemTime>)> {
+    use crate::sys::unix::timestamp;
+
+    let (n, addr, time) = timestamp::recv_timestamped(socket.as_raw_fd(), buf)?;
+    // This is safe because `recvmsg` initialised the address.
+    unsafe { to_socket_addr(&addr) }.map(|addr| (n, addr, time))
+}
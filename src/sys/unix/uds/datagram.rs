@@ -1,23 +1,55 @@
-use super::{socket_addr, SocketAddr};
+use super::{path_offset, socket_addr, SocketAddr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::UCred;
+use crate::net::{ReceivedFds, RecvMsg, SendMsg};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::cmsg;
+use crate::sys::unix::mmsg;
 use crate::sys::unix::net::new_socket;
 
-use std::io;
-use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
 use std::os::unix::net;
 use std::path::Path;
+use std::{cmp, io, mem, ptr};
 
 pub(crate) fn bind(path: &Path) -> io::Result<net::UnixDatagram> {
-    let fd = new_socket(libc::AF_UNIX, libc::SOCK_DGRAM)?;
+    let (sockaddr, socklen) = socket_addr(path)?;
+    bind_addr(&SocketAddr::from_parts(sockaddr, socklen))
+}
+
+pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<net::UnixDatagram> {
+    let fd = new_socket(libc::AF_UNIX, libc::SOCK_DGRAM, 0)?;
     // Ensure the fd is closed.
     let socket = unsafe { net::UnixDatagram::from_raw_fd(fd) };
-    let (sockaddr, socklen) = socket_addr(path)?;
-    let sockaddr = &sockaddr as *const libc::sockaddr_un as *const _;
+    let (sockaddr, socklen) = address.as_raw();
     syscall!(bind(fd, sockaddr, socklen))?;
     Ok(socket)
 }
 
+pub(crate) fn connect_addr(socket: &net::UnixDatagram, address: &SocketAddr) -> io::Result<()> {
+    let (sockaddr, socklen) = address.as_raw();
+    syscall!(connect(socket.as_raw_fd(), sockaddr, socklen)).map(|_| ())
+}
+
+pub(crate) fn send_to_addr(
+    socket: &net::UnixDatagram,
+    buf: &[u8],
+    address: &SocketAddr,
+) -> io::Result<usize> {
+    let (sockaddr, socklen) = address.as_raw();
+    syscall!(sendto(
+        socket.as_raw_fd(),
+        buf.as_ptr() as *const libc::c_void,
+        buf.len(),
+        0,
+        sockaddr,
+        socklen,
+    ))
+    .map(|n| n as usize)
+}
+
 pub(crate) fn unbound() -> io::Result<net::UnixDatagram> {
-    new_socket(libc::AF_UNIX, libc::SOCK_DGRAM)
+    new_socket(libc::AF_UNIX, libc::SOCK_DGRAM, 0)
         .map(|socket| unsafe { net::UnixDatagram::from_raw_fd(socket) })
 }
 
@@ -54,3 +86,113 @@ pub(crate) fn recv_from(
     })?;
     Ok((count as usize, socketaddr))
 }
+
+pub(crate) fn send_batch(
+    socket: &net::UnixDatagram,
+    msgs: &[SendMsg<'_, &Path>],
+) -> io::Result<usize> {
+    mmsg::send_batch(socket.as_raw_fd(), msgs, |path| {
+        let (sockaddr, socklen) = socket_addr(path)?;
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &sockaddr as *const libc::sockaddr_un,
+                &mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_un,
+                1,
+            )
+        };
+        Ok((storage, socklen))
+    })
+}
+
+pub(crate) fn recv_batch(
+    socket: &net::UnixDatagram,
+    msgs: &mut [RecvMsg<'_, SocketAddr>],
+) -> io::Result<usize> {
+    mmsg::recv_batch(socket.as_raw_fd(), msgs, |storage, socklen| {
+        let sockaddr =
+            unsafe { *(storage as *const libc::sockaddr_storage as *const libc::sockaddr_un) };
+        // Unnamed senders may have an empty address.
+        let socklen = cmp::max(socklen, path_offset(&sockaddr) as libc::socklen_t);
+        Ok(SocketAddr::from_parts(sockaddr, socklen))
+    })
+}
+
+pub(crate) fn send_with_fds(
+    socket: &net::UnixDatagram,
+    buf: &[u8],
+    fds: &[RawFd],
+) -> io::Result<usize> {
+    super::send_with_fds(socket.as_raw_fd(), buf, fds)
+}
+
+pub(crate) fn recv_with_fds(
+    socket: &net::UnixDatagram,
+    buf: &mut [u8],
+    max_fds: usize,
+) -> io::Result<(usize, ReceivedFds)> {
+    super::recv_with_fds(socket.as_raw_fd(), buf, max_fds)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_passcred(socket: &net::UnixDatagram, on: bool) -> io::Result<()> {
+    let val = on as libc::c_int;
+    syscall!(setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_PASSCRED,
+        &val as *const libc::c_int as *const libc::c_void,
+        mem::size_of::<libc::c_int>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn passcred(socket: &net::UnixDatagram) -> io::Result<bool> {
+    let mut val: libc::c_int = 0;
+    let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_PASSCRED,
+        &mut val as *mut libc::c_int as *mut libc::c_void,
+        &mut len,
+    ))
+    .map(|_| val != 0)
+}
+
+/// Receives a datagram into `dst`, returning the number of bytes read, the
+/// address of the sender and its credentials, if `SO_PASSCRED` is enabled.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_from_with_cred(
+    socket: &net::UnixDatagram,
+    dst: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<UCred>)> {
+    let mut count = 0;
+    let mut cred = None;
+    let socketaddr = SocketAddr::new(|sockaddr, socklen| {
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = sockaddr as *mut libc::c_void;
+        msg.msg_namelen = *socklen;
+        let mut iov = libc::iovec {
+            iov_base: dst.as_mut_ptr() as *mut libc::c_void,
+            iov_len: dst.len(),
+        };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        let mut control = cmsg::Buffer::with_capacity(cmsg::space(mem::size_of::<libc::ucred>()));
+        control.apply(&mut msg);
+
+        let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+        count = n as usize;
+        // Unnamed senders may have an empty address.
+        let offset = path_offset(unsafe { &*(sockaddr as *const libc::sockaddr_un) });
+        *socklen = cmp::max(msg.msg_namelen, offset as libc::socklen_t);
+        cred = unsafe { cmsg::messages(&msg) }
+            .find(|&(level, ty, _)| level == libc::SOL_SOCKET && ty == libc::SCM_CREDENTIALS)
+            .and_then(|(_, _, data)| cmsg::read::<libc::ucred>(data))
+            .map(|cred| UCred::new(cred.uid, cred.gid, Some(cred.pid)));
+        Ok(n as libc::c_int)
+    })?;
+    Ok((count, socketaddr, cred))
+}
@@ -14,14 +14,29 @@ pub(in crate::sys) fn path_offset(sockaddr: &libc::sockaddr_un) -> usize {
 }
 
 cfg_os_poll! {
+    use crate::net::{ReceivedFd, ReceivedFds};
+    use crate::sys::unix::cmsg;
+    use crate::sys::unix::net::new_socket;
+
     use std::cmp::Ordering;
     use std::os::unix::ffi::OsStrExt;
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    )))]
+    use std::os::unix::io::AsRawFd;
     use std::os::unix::io::{RawFd, FromRawFd};
     use std::path::Path;
-    use std::{io, mem};
+    use std::{io, mem, slice};
 
     pub(crate) mod datagram;
     pub(crate) mod listener;
+    #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+    pub(crate) mod seqpacket;
     pub(crate) mod stream;
 
     pub(in crate::sys) fn socket_addr(path: &Path) -> io::Result<(libc::sockaddr_un, libc::socklen_t)> {
@@ -100,6 +115,106 @@ cfg_os_poll! {
         Ok(pair)
     }
 
+    /// Creates a socket of type `socket_type` bound to `address` that listens
+    /// for incoming connections.
+    fn listen<T>(socket_type: libc::c_int, address: &SocketAddr) -> io::Result<T>
+        where T: FromRawFd,
+    {
+        let socket = new_socket(libc::AF_UNIX, socket_type, 0)?;
+        let (sockaddr, socklen) = address.as_raw();
+
+        syscall!(bind(socket, sockaddr, socklen))
+            .and_then(|_| syscall!(listen(socket, 1024)))
+            .map_err(|err| {
+                // Close the socket if we hit an error, ignoring the error from
+                // closing since we can't pass back two errors.
+                let _ = unsafe { libc::close(socket) };
+                err
+            })
+            .map(|_| unsafe { T::from_raw_fd(socket) })
+    }
+
+    /// Creates a socket of type `socket_type` and starts connecting it to
+    /// `address`.
+    fn connect<T>(socket_type: libc::c_int, address: &SocketAddr) -> io::Result<T>
+        where T: FromRawFd,
+    {
+        let socket = new_socket(libc::AF_UNIX, socket_type, 0)?;
+        let (sockaddr, socklen) = address.as_raw();
+
+        match syscall!(connect(socket, sockaddr, socklen)) {
+            Ok(_) => {}
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            Err(e) => {
+                // Close the socket if we hit an error, ignoring the error
+                // from closing since we can't pass back two errors.
+                let _ = unsafe { libc::close(socket) };
+
+                return Err(e);
+            }
+        }
+
+        Ok(unsafe { T::from_raw_fd(socket) })
+    }
+
+    /// Accepts a connection on `listener`, returning the new socket in
+    /// non-blocking mode with the close-on-exec flag set.
+    fn accept<T>(listener: RawFd) -> io::Result<(T, SocketAddr)>
+        where T: FromRawFd,
+    {
+        let sockaddr = mem::MaybeUninit::<libc::sockaddr_un>::zeroed();
+
+        // This is safe to assume because a `libc::sockaddr_un` filled with `0`
+        // bytes is properly initialized.
+        //
+        // `0` is a valid value for `sockaddr_un::sun_family`; it is
+        // `libc::AF_UNSPEC`.
+        //
+        // `[0; 108]` is a valid value for `sockaddr_un::sun_path`; it begins an
+        // abstract path.
+        let mut sockaddr = unsafe { sockaddr.assume_init() };
+
+        sockaddr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+        let mut socklen = mem::size_of_val(&sockaddr) as libc::socklen_t;
+
+        #[cfg(not(any(
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "solaris"
+        )))]
+        let socket = {
+            let flags = libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC;
+            syscall!(accept4(
+                listener,
+                &mut sockaddr as *mut libc::sockaddr_un as *mut libc::sockaddr,
+                &mut socklen,
+                flags
+            ))
+            .map(|socket| unsafe { T::from_raw_fd(socket) })
+        };
+
+        #[cfg(any(
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "solaris"
+        ))]
+        let socket = syscall!(accept(
+            listener,
+            &mut sockaddr as *mut libc::sockaddr_un as *mut libc::sockaddr,
+            &mut socklen,
+        ))
+        .and_then(|socket| {
+            // Ensure the socket is closed if either of the `fcntl` calls
+            // error below.
+            let s = unsafe { T::from_raw_fd(socket) };
+            syscall!(fcntl(socket, libc::F_SETFD, libc::FD_CLOEXEC)).map(|_| s)
+        });
+
+        socket.map(|socket| (socket, SocketAddr::from_parts(sockaddr, socklen)))
+    }
+
     // The following functions can't simply be replaced with a call to
     // `net::UnixDatagram` because of our `SocketAddr` type.
 
@@ -111,6 +226,101 @@ cfg_os_poll! {
         SocketAddr::new(|sockaddr, socklen| syscall!(getpeername(socket, sockaddr, socklen)))
     }
 
+    /// Sends `buf` along with the file descriptors in `fds` using
+    /// `SCM_RIGHTS`.
+    fn send_with_fds(socket: RawFd, buf: &[u8], fds: &[RawFd]) -> io::Result<usize> {
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        let mut control = cmsg::Buffer::new();
+        if !fds.is_empty() {
+            let data = unsafe {
+                slice::from_raw_parts(fds.as_ptr() as *const u8, mem::size_of_val(fds))
+            };
+            control.push(libc::SOL_SOCKET, libc::SCM_RIGHTS, data);
+        }
+        control.apply(&mut msg);
+
+        syscall!(sendmsg(socket, &msg, 0)).map(|n| n as usize)
+    }
+
+    /// Receives data into `buf` along with at most `max_fds` file
+    /// descriptors passed using `SCM_RIGHTS`.
+    fn recv_with_fds(
+        socket: RawFd,
+        buf: &mut [u8],
+        max_fds: usize,
+    ) -> io::Result<(usize, ReceivedFds)> {
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        let mut control = if max_fds == 0 {
+            cmsg::Buffer::new()
+        } else {
+            // Not using `cmsg::space` here, as its padding could leave room
+            // for more than `max_fds` file descriptors.
+            cmsg::Buffer::with_capacity(cmsg::len(max_fds * mem::size_of::<RawFd>()))
+        };
+        control.apply(&mut msg);
+
+        #[cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "linux",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        ))]
+        let flags = libc::MSG_CMSG_CLOEXEC;
+        #[cfg(not(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "linux",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        )))]
+        let flags = 0;
+
+        let n = syscall!(recvmsg(socket, &mut msg, flags))?;
+        // Take ownership of all received file descriptors first, so they are
+        // closed if anything below fails.
+        let mut fds = Vec::new();
+        for (level, ty, data) in unsafe { cmsg::messages(&msg) } {
+            if level == libc::SOL_SOCKET && ty == libc::SCM_RIGHTS {
+                let received = data
+                    .chunks(mem::size_of::<RawFd>())
+                    .filter_map(cmsg::read::<RawFd>)
+                    .map(|fd| unsafe { ReceivedFd::from_raw_fd(fd) });
+                fds.extend(received);
+            }
+        }
+
+        // Darwin and Solaris don't support `MSG_CMSG_CLOEXEC`.
+        #[cfg(not(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "linux",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        )))]
+        for fd in &fds {
+            syscall!(fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC))?;
+        }
+
+        let truncated = msg.msg_flags & libc::MSG_CTRUNC != 0;
+        Ok((n as usize, ReceivedFds::new(fds, truncated)))
+    }
+
     #[cfg(test)]
     mod tests {
         use super::{path_offset, socket_addr};
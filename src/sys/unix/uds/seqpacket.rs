@@ -0,0 +1,98 @@
+use super::{socket_addr, SocketAddr};
+
+use std::net::Shutdown;
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::path::Path;
+use std::{io, mem};
+
+pub(crate) fn bind<T: FromRawFd>(path: &Path) -> io::Result<T> {
+    let (sockaddr, socklen) = socket_addr(path)?;
+    bind_addr(&SocketAddr::from_parts(sockaddr, socklen))
+}
+
+pub(crate) fn bind_addr<T: FromRawFd>(address: &SocketAddr) -> io::Result<T> {
+    super::listen(libc::SOCK_SEQPACKET, address)
+}
+
+pub(crate) fn accept<T: FromRawFd>(listener: RawFd) -> io::Result<(T, SocketAddr)> {
+    super::accept(listener)
+}
+
+pub(crate) fn connect<T: FromRawFd>(path: &Path) -> io::Result<T> {
+    let (sockaddr, socklen) = socket_addr(path)?;
+    connect_addr(&SocketAddr::from_parts(sockaddr, socklen))
+}
+
+pub(crate) fn connect_addr<T: FromRawFd>(address: &SocketAddr) -> io::Result<T> {
+    super::connect(libc::SOCK_SEQPACKET, address)
+}
+
+pub(crate) fn pair<T: FromRawFd>() -> io::Result<(T, T)> {
+    super::pair(libc::SOCK_SEQPACKET)
+}
+
+pub(crate) fn local_addr(socket: RawFd) -> io::Result<SocketAddr> {
+    super::local_addr(socket)
+}
+
+pub(crate) fn peer_addr(socket: RawFd) -> io::Result<SocketAddr> {
+    super::peer_addr(socket)
+}
+
+pub(crate) fn send(socket: RawFd, buf: &[u8]) -> io::Result<usize> {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let flags = libc::MSG_NOSIGNAL;
+    // Other platforms either set `SO_NOSIGPIPE` in `new_socket` or don't
+    // support `MSG_NOSIGNAL`.
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let flags = 0;
+
+    syscall!(send(
+        socket,
+        buf.as_ptr() as *const libc::c_void,
+        buf.len(),
+        flags,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Receives a single message into `buf`, returning the number of bytes read
+/// and whether the message was truncated because `buf` was too small.
+pub(crate) fn recv(socket: RawFd, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+
+    let n = syscall!(recvmsg(socket, &mut msg, 0))?;
+    Ok((n as usize, msg.msg_flags & libc::MSG_TRUNC != 0))
+}
+
+pub(crate) fn shutdown(socket: RawFd, how: Shutdown) -> io::Result<()> {
+    let how = match how {
+        Shutdown::Read => libc::SHUT_RD,
+        Shutdown::Write => libc::SHUT_WR,
+        Shutdown::Both => libc::SHUT_RDWR,
+    };
+    syscall!(shutdown(socket, how)).map(|_| ())
+}
+
+pub(crate) fn take_error(socket: RawFd) -> io::Result<Option<io::Error>> {
+    let mut error: libc::c_int = 0;
+    let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket,
+        libc::SOL_SOCKET,
+        libc::SO_ERROR,
+        &mut error as *mut libc::c_int as *mut libc::c_void,
+        &mut len,
+    ))?;
+    if error == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(io::Error::from_raw_os_error(error)))
+    }
+}
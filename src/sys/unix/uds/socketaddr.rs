@@ -28,7 +28,9 @@ enum AddressKind<'a> {
 impl SocketAddr {
     fn address(&self) -> AddressKind<'_> {
         let offset = path_offset(&self.sockaddr);
-        let len = self.socklen as usize - offset;
+        // Unnamed addresses may be reported with a length shorter than the
+        // offset of `sun_path`.
+        let len = (self.socklen as usize).saturating_sub(offset);
         let path = unsafe { &*(&self.sockaddr.sun_path as *const [libc::c_char] as *const [u8]) };
 
         // macOS seems to return a len of 16 and a zeroed sun_path for unnamed addresses
@@ -46,6 +48,7 @@ impl SocketAddr {
 }
 
 cfg_os_poll! {
+    use super::socket_addr;
     use std::{io, mem};
 
     impl SocketAddr {
@@ -69,6 +72,58 @@ cfg_os_poll! {
             SocketAddr { sockaddr, socklen }
         }
 
+        pub(crate) fn as_raw(&self) -> (*const libc::sockaddr, libc::socklen_t) {
+            (
+                &self.sockaddr as *const libc::sockaddr_un as *const libc::sockaddr,
+                self.socklen,
+            )
+        }
+
+        /// Creates an address from a pathname.
+        ///
+        /// Returns an error if the path is too long, or if it starts with a
+        /// null byte.
+        pub fn from_pathname<P: AsRef<Path>>(path: P) -> io::Result<SocketAddr> {
+            let path = path.as_ref();
+            if path.as_os_str().as_bytes().first() == Some(&0) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "pathname must not start with a null byte",
+                ));
+            }
+            let (sockaddr, socklen) = socket_addr(path)?;
+            Ok(SocketAddr::from_parts(sockaddr, socklen))
+        }
+
+        /// Creates an address in the abstract namespace from `name`.
+        ///
+        /// Abstract addresses don't exist on the file system, so they don't
+        /// need to be removed after use. The name may contain any bytes,
+        /// including null bytes. Returns an error if the name is too long.
+        ///
+        /// Only available on Linux and Android.
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        pub fn from_abstract_name<N: AsRef<[u8]>>(name: N) -> io::Result<SocketAddr> {
+            let name = name.as_ref();
+            let mut sockaddr = {
+                let sockaddr = mem::MaybeUninit::<libc::sockaddr_un>::zeroed();
+                unsafe { sockaddr.assume_init() }
+            };
+            sockaddr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+            // The first byte of `sun_path` is left as a null byte.
+            if name.len() >= sockaddr.sun_path.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "abstract name must be shorter than libc::sockaddr_un.sun_path",
+                ));
+            }
+            for (dst, src) in sockaddr.sun_path[1..].iter_mut().zip(name.iter()) {
+                *dst = *src as libc::c_char;
+            }
+            let socklen = path_offset(&sockaddr) + 1 + name.len();
+            Ok(SocketAddr::from_parts(sockaddr, socklen as libc::socklen_t))
+        }
+
         /// Returns `true` if the address is unnamed.
         ///
         /// Documentation reflected in [`SocketAddr`]
@@ -94,6 +149,19 @@ cfg_os_poll! {
                 None
             }
         }
+
+        /// Returns the contents of this address if it is in the abstract
+        /// namespace.
+        ///
+        /// Only available on Linux and Android.
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        pub fn as_abstract_name(&self) -> Option<&[u8]> {
+            if let AddressKind::Abstract(name) = self.address() {
+                Some(name)
+            } else {
+                None
+            }
+        }
     }
 }
 
@@ -1,29 +1,22 @@
 use super::{socket_addr, SocketAddr};
-use crate::sys::unix::net::new_socket;
+use crate::net::ReceivedFds;
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+use crate::net::UCred;
 
 use std::io;
-use std::os::unix::io::{AsRawFd, FromRawFd};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::mem;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::os::unix::net;
 use std::path::Path;
 
 pub(crate) fn connect(path: &Path) -> io::Result<net::UnixStream> {
-    let socket = new_socket(libc::AF_UNIX, libc::SOCK_STREAM)?;
     let (sockaddr, socklen) = socket_addr(path)?;
-    let sockaddr = &sockaddr as *const libc::sockaddr_un as *const libc::sockaddr;
-
-    match syscall!(connect(socket, sockaddr, socklen)) {
-        Ok(_) => {}
-        Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
-        Err(e) => {
-            // Close the socket if we hit an error, ignoring the error
-            // from closing since we can't pass back two errors.
-            let _ = unsafe { libc::close(socket) };
-
-            return Err(e);
-        }
-    }
+    connect_addr(&SocketAddr::from_parts(sockaddr, socklen))
+}
 
-    Ok(unsafe { net::UnixStream::from_raw_fd(socket) })
+pub(crate) fn connect_addr(address: &SocketAddr) -> io::Result<net::UnixStream> {
+    super::connect(libc::SOCK_STREAM, address)
 }
 
 pub(crate) fn pair() -> io::Result<(net::UnixStream, net::UnixStream)> {
@@ -37,3 +30,46 @@ pub(crate) fn local_addr(socket: &net::UnixStream) -> io::Result<SocketAddr> {
 pub(crate) fn peer_addr(socket: &net::UnixStream) -> io::Result<SocketAddr> {
     super::peer_addr(socket.as_raw_fd())
 }
+
+pub(crate) fn send_with_fds(
+    socket: &net::UnixStream,
+    buf: &[u8],
+    fds: &[RawFd],
+) -> io::Result<usize> {
+    super::send_with_fds(socket.as_raw_fd(), buf, fds)
+}
+
+pub(crate) fn recv_with_fds(
+    socket: &net::UnixStream,
+    buf: &mut [u8],
+    max_fds: usize,
+) -> io::Result<(usize, ReceivedFds)> {
+    super::recv_with_fds(socket.as_raw_fd(), buf, max_fds)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn peer_cred(socket: &net::UnixStream) -> io::Result<UCred> {
+    let mut cred: libc::ucred = unsafe { mem::zeroed() };
+    let mut len = mem::size_of::<libc::ucred>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_PEERCRED,
+        &mut cred as *mut libc::ucred as *mut libc::c_void,
+        &mut len,
+    ))?;
+    Ok(UCred::new(cred.uid, cred.gid, Some(cred.pid)))
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "linux",
+    target_os = "solaris",
+    target_os = "illumos"
+)))]
+pub(crate) fn peer_cred(socket: &net::UnixStream) -> io::Result<UCred> {
+    let mut uid = 0;
+    let mut gid = 0;
+    syscall!(getpeereid(socket.as_raw_fd(), &mut uid, &mut gid))?;
+    Ok(UCred::new(uid, gid, None))
+}
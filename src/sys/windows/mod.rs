@@ -86,9 +86,15 @@ cfg_net! {
             if let Err(ref e) = result {
                 if e.kind() == io::ErrorKind::WouldBlock {
                     self.inner.as_ref().map_or(Ok(()), |state| {
-                        state
-                            .selector
-                            .reregister(state.sock_state.clone(), state.token, state.interests)
+                        if state.interests.is_oneshot() {
+                            // Oneshot registrations are only rearmed by the
+                            // user.
+                            Ok(())
+                        } else {
+                            state
+                                .selector
+                                .reregister(state.sock_state.clone(), state.token, state.interests)
+                        }
                     })?;
                 }
             }
@@ -97,6 +97,10 @@ pub struct SockState {
     pending_evts: u32,
 
     user_data: u64,
+    // Disarm the socket after an event is returned, see `Interest::ONESHOT`.
+    oneshot: bool,
+    // Don't reset the interests after an event, see `Interest::LEVEL`.
+    level: bool,
 
     poll_status: SockPollStatus,
     delete_pending: bool,
@@ -223,17 +227,27 @@ impl SockState {
             return None;
         }
 
-        // In mio, we have to simulate Edge-triggered behavior to match API usage.
-        // The strategy here is to intercept all read/write from user that could cause WouldBlock usage,
-        // then reregister the socket to reset the interests.
+        if self.oneshot {
+            // Disarm the socket, the user must reregister it to receive more
+            // events.
+            self.user_evts = 0;
+        } else if self.level {
+            // Keep all interests, the socket is polled again (it's added to
+            // the update queue in `feed_events`) and will return another
+            // event if it's still ready.
+        } else {
+            // In mio, we have to simulate Edge-triggered behavior to match API usage.
+            // The strategy here is to intercept all read/write from user that could cause WouldBlock usage,
+            // then reregister the socket to reset the interests.
 
-        // Reset readable event
-        if (afd_events & interests_to_afd_flags(Interest::READABLE)) != 0 {
-            self.user_evts &= !(interests_to_afd_flags(Interest::READABLE));
-        }
-        // Reset writable event
-        if (afd_events & interests_to_afd_flags(Interest::WRITABLE)) != 0 {
-            self.user_evts &= !interests_to_afd_flags(Interest::WRITABLE);
+            // Reset readable event
+            if (afd_events & interests_to_afd_flags(Interest::READABLE)) != 0 {
+                self.user_evts &= !(interests_to_afd_flags(Interest::READABLE));
+            }
+            // Reset writable event
+            if (afd_events & interests_to_afd_flags(Interest::WRITABLE)) != 0 {
+                self.user_evts &= !interests_to_afd_flags(Interest::WRITABLE);
+            }
         }
 
         Some(Event {
@@ -273,6 +287,8 @@ cfg_net! {
                 user_evts: 0,
                 pending_evts: 0,
                 user_data: 0,
+                oneshot: false,
+                level: false,
                 poll_status: SockPollStatus::Idle,
                 delete_pending: false,
                 error: None,
@@ -281,12 +297,14 @@ cfg_net! {
         }
 
         /// True if need to be added on update queue, false otherwise.
-        fn set_event(&mut self, ev: Event) -> bool {
+        fn set_event(&mut self, ev: Event, interests: Interest) -> bool {
             /* afd::POLL_CONNECT_FAIL and afd::POLL_ABORT are always reported, even when not requested by the caller. */
             let events = ev.flags | afd::POLL_CONNECT_FAIL | afd::POLL_ABORT;
 
             self.user_evts = events;
             self.user_data = ev.data;
+            self.oneshot = interests.is_oneshot();
+            self.level = interests.is_level();
 
             (events & !self.pending_evts) != 0
         }
@@ -436,6 +454,9 @@ impl SelectorInner {
     /// May only be calling via `Selector::select`.
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
         events.clear();
+        // `GetQueuedCompletionStatusEx` only supports millisecond precision,
+        // round up to make sure we don't return before the timeout elapsed.
+        let timeout = timeout.map(round_up_millis);
 
         if timeout.is_none() {
             loop {
@@ -552,7 +573,7 @@ cfg_net! {
                     flags,
                     data: token.0 as u64,
                 };
-                sock.lock().unwrap().set_event(event);
+                sock.lock().unwrap().set_event(event, interests);
                 sock
             };
 
@@ -582,7 +603,7 @@ cfg_net! {
                     data: token.0 as u64,
                 };
 
-                state.lock().unwrap().set_event(event);
+                state.lock().unwrap().set_event(event, interests);
             }
 
             // FIXME: a sock which has_error true should not be re-added to
@@ -743,3 +764,15 @@ fn interests_to_afd_flags(interests: Interest) -> u32 {
 
     flags
 }
+
+/// Rounds `duration` up to whole milliseconds.
+fn round_up_millis(duration: Duration) -> Duration {
+    const NANOS_PER_MILLI: u32 = 1_000_000;
+    match duration.checked_add(Duration::from_nanos(u64::from(NANOS_PER_MILLI - 1))) {
+        Some(d) => Duration::new(
+            d.as_secs(),
+            d.subsec_nanos() / NANOS_PER_MILLI * NANOS_PER_MILLI,
+        ),
+        None => duration,
+    }
+}
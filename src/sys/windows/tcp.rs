@@ -1,68 +1,241 @@
 use std::io;
-use std::net::{self, SocketAddr};
+use std::mem::size_of;
+use std::net::{self, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::os::windows::io::FromRawSocket;
 use std::os::windows::raw::SOCKET as StdSocket; // winapi uses usize, stdlib uses u32/u64.
+use std::time::Duration;
 
+use winapi::ctypes::{c_char, c_int};
+use winapi::shared::ws2def::{
+    IPPROTO_TCP, SOCKADDR_STORAGE, SOL_SOCKET, SO_KEEPALIVE, SO_RCVBUF, SO_REUSEADDR, SO_SNDBUF,
+};
 use winapi::um::winsock2::{
-    bind as win_bind, closesocket, connect as win_connect, listen, SOCKET_ERROR, SOCK_STREAM,
+    bind as win_bind, closesocket, connect as win_connect, getsockname, getsockopt,
+    listen as win_listen, setsockopt, SOCKET, SOCKET_ERROR, SOCK_STREAM,
 };
 
 use crate::sys::windows::net::{inaddr_any, init, new_socket, socket_addr};
 
-pub fn connect(addr: SocketAddr) -> io::Result<net::TcpStream> {
+pub(crate) type TcpSocket = SOCKET;
+
+pub(crate) fn new_v4_socket() -> io::Result<TcpSocket> {
+    new_for_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
+}
+
+pub(crate) fn new_v6_socket() -> io::Result<TcpSocket> {
+    new_for_addr(SocketAddr::V6(SocketAddrV6::new(
+        Ipv6Addr::UNSPECIFIED,
+        0,
+        0,
+        0,
+    )))
+}
+
+pub(crate) fn new_for_addr(addr: SocketAddr) -> io::Result<TcpSocket> {
     init();
     new_socket(addr, SOCK_STREAM)
-        .and_then(|socket| {
-            // Required for a future `connect_overlapped` operation to be
-            // executed successfully.
-            let any_addr = inaddr_any(addr);
-            let (raw_addr, raw_addr_length) = socket_addr(&any_addr);
-            syscall!(
-                win_bind(socket, raw_addr, raw_addr_length),
-                PartialEq::eq,
-                SOCKET_ERROR
-            )
-            .and_then(|_| {
-                let (raw_addr, raw_addr_length) = socket_addr(&addr);
-                syscall!(
-                    win_connect(socket, raw_addr, raw_addr_length),
-                    PartialEq::eq,
-                    SOCKET_ERROR
-                )
-                .or_else(|err| match err {
-                    ref err if err.kind() == io::ErrorKind::WouldBlock => Ok(0),
-                    err => Err(err),
-                })
-            })
-            .map(|_| socket)
-            .map_err(|err| {
-                // Close the socket if we hit an error, ignoring the error
-                // from closing since we can't pass back two errors.
-                let _ = unsafe { closesocket(socket) };
-                err
-            })
-        })
-        .map(|socket| unsafe { net::TcpStream::from_raw_socket(socket as StdSocket) })
-}
-
-pub fn bind(addr: SocketAddr) -> io::Result<net::TcpListener> {
-    init();
-    new_socket(addr, SOCK_STREAM).and_then(|socket| {
-        let (raw_addr, raw_addr_length) = socket_addr(&addr);
-        syscall!(
-            win_bind(socket, raw_addr, raw_addr_length,),
-            PartialEq::eq,
-            SOCKET_ERROR
-        )
-        .and_then(|_| syscall!(listen(socket, 1024), PartialEq::eq, SOCKET_ERROR))
-        .map_err(|err| {
-            // Close the socket if we hit an error, ignoring the error
-            // from closing since we can't pass back two errors.
-            let _ = unsafe { closesocket(socket) };
-            err
-        })
-        .map(|_| unsafe { net::TcpListener::from_raw_socket(socket as StdSocket) })
-    })
+}
+
+pub(crate) fn bind(socket: TcpSocket, addr: SocketAddr) -> io::Result<()> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(
+        win_bind(socket, raw_addr, raw_addr_length),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(())
+}
+
+pub(crate) fn connect(socket: TcpSocket, addr: SocketAddr) -> io::Result<net::TcpStream> {
+    // Required for a future `connect_overlapped` operation to be executed
+    // successfully, so bind the socket if the user didn't already.
+    if get_localaddr(socket).is_err() {
+        bind(socket, inaddr_any(addr))?;
+    }
+
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    match syscall!(
+        win_connect(socket, raw_addr, raw_addr_length),
+        PartialEq::eq,
+        SOCKET_ERROR
+    ) {
+        Err(err) if err.kind() != io::ErrorKind::WouldBlock => Err(err),
+        _ => Ok(unsafe { net::TcpStream::from_raw_socket(socket as StdSocket) }),
+    }
+}
+
+/// Returns `Ok(true)` if the connection is established and `Ok(false)` while
+/// it's still being established.
+pub(crate) fn connect_status(stream: &net::TcpStream) -> io::Result<bool> {
+    // Check `SO_ERROR` first, `getpeername` returns `WSAENOTCONN` for a
+    // failed connect as well.
+    match stream.take_error() {
+        Ok(Some(err)) | Err(err) => return Err(err),
+        Ok(None) => {}
+    }
+
+    match stream.peer_addr() {
+        Ok(_) => Ok(true),
+        // Windows can't tell a connect that is in progress apart from one
+        // that failed and whose error was already taken.
+        Err(ref err) if err.kind() == io::ErrorKind::NotConnected => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+pub(crate) fn listen(socket: TcpSocket, backlog: u32) -> io::Result<net::TcpListener> {
+    let backlog = std::cmp::min(backlog, c_int::max_value() as u32) as c_int;
+    syscall!(win_listen(socket, backlog), PartialEq::eq, SOCKET_ERROR)?;
+    Ok(unsafe { net::TcpListener::from_raw_socket(socket as StdSocket) })
+}
+
+pub(crate) fn close(socket: TcpSocket) {
+    let _ = unsafe { closesocket(socket) };
+}
+
+pub(crate) fn set_reuseaddr(socket: TcpSocket, reuseaddr: bool) -> io::Result<()> {
+    set_opt(socket, SOL_SOCKET, SO_REUSEADDR, reuseaddr as c_int)
+}
+
+pub(crate) fn get_reuseaddr(socket: TcpSocket) -> io::Result<bool> {
+    get_opt(socket, SOL_SOCKET, SO_REUSEADDR).map(|value| value != 0)
+}
+
+pub(crate) fn set_send_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = std::cmp::min(size, c_int::max_value() as u32) as c_int;
+    set_opt(socket, SOL_SOCKET, SO_SNDBUF, size)
+}
+
+pub(crate) fn get_send_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    get_opt(socket, SOL_SOCKET, SO_SNDBUF).map(|size| size as u32)
+}
+
+pub(crate) fn set_recv_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = std::cmp::min(size, c_int::max_value() as u32) as c_int;
+    set_opt(socket, SOL_SOCKET, SO_RCVBUF, size)
+}
+
+pub(crate) fn get_recv_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    get_opt(socket, SOL_SOCKET, SO_RCVBUF).map(|size| size as u32)
+}
+
+pub(crate) fn set_keepalive(socket: TcpSocket, keepalive: bool) -> io::Result<()> {
+    set_opt(socket, SOL_SOCKET, SO_KEEPALIVE, keepalive as c_int)
+}
+
+pub(crate) fn get_keepalive(socket: TcpSocket) -> io::Result<bool> {
+    get_opt(socket, SOL_SOCKET, SO_KEEPALIVE).map(|value| value != 0)
+}
+
+// Not defined by winapi, values taken from `ws2ipdef.h`. Supported since
+// Windows 10, version 1709.
+const TCP_KEEPIDLE: c_int = 3;
+const TCP_KEEPCNT: c_int = 16;
+const TCP_KEEPINTVL: c_int = 17;
+
+pub(crate) fn set_keepalive_time(socket: TcpSocket, time: Duration) -> io::Result<()> {
+    set_opt(socket, IPPROTO_TCP as c_int, TCP_KEEPIDLE, secs(time))
+}
+
+pub(crate) fn get_keepalive_time(socket: TcpSocket) -> io::Result<Duration> {
+    get_opt(socket, IPPROTO_TCP as c_int, TCP_KEEPIDLE).map(|secs| Duration::from_secs(secs as u64))
+}
+
+pub(crate) fn set_keepalive_interval(socket: TcpSocket, interval: Duration) -> io::Result<()> {
+    set_opt(socket, IPPROTO_TCP as c_int, TCP_KEEPINTVL, secs(interval))
+}
+
+pub(crate) fn get_keepalive_interval(socket: TcpSocket) -> io::Result<Duration> {
+    get_opt(socket, IPPROTO_TCP as c_int, TCP_KEEPINTVL)
+        .map(|secs| Duration::from_secs(secs as u64))
+}
+
+pub(crate) fn set_keepalive_retries(socket: TcpSocket, retries: u32) -> io::Result<()> {
+    let retries = std::cmp::min(retries, c_int::max_value() as u32) as c_int;
+    set_opt(socket, IPPROTO_TCP as c_int, TCP_KEEPCNT, retries)
+}
+
+pub(crate) fn get_keepalive_retries(socket: TcpSocket) -> io::Result<u32> {
+    get_opt(socket, IPPROTO_TCP as c_int, TCP_KEEPCNT).map(|retries| retries as u32)
+}
+
+/// Converts `duration` to whole seconds for the keepalive options.
+fn secs(duration: Duration) -> c_int {
+    std::cmp::min(duration.as_secs(), c_int::max_value() as u64) as c_int
+}
+
+pub(crate) fn get_localaddr(socket: TcpSocket) -> io::Result<SocketAddr> {
+    let mut storage: SOCKADDR_STORAGE = unsafe { std::mem::zeroed() };
+    let mut length = size_of::<SOCKADDR_STORAGE>() as c_int;
+
+    syscall!(
+        getsockname(socket, &mut storage as *mut _ as *mut _, &mut length),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+
+    // This is safe because `getsockname` initialised the address.
+    unsafe { to_socket_addr(&storage) }
+}
+
+/// `storage` must be initialised to `SOCKADDR_IN` or `SOCKADDR_IN6`.
+unsafe fn to_socket_addr(storage: *const SOCKADDR_STORAGE) -> io::Result<SocketAddr> {
+    use winapi::shared::ws2def::{AF_INET, AF_INET6, SOCKADDR_IN};
+    use winapi::shared::ws2ipdef::SOCKADDR_IN6_LH;
+
+    match (*storage).ss_family as c_int {
+        AF_INET => {
+            let addr = &*(storage as *const SOCKADDR_IN);
+            let ip = Ipv4Addr::from(u32::from_be(*addr.sin_addr.S_un.S_addr()));
+            let port = u16::from_be(addr.sin_port);
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        }
+        AF_INET6 => {
+            let addr = &*(storage as *const SOCKADDR_IN6_LH);
+            let ip = Ipv6Addr::from(*addr.sin6_addr.u.Byte());
+            let port = u16::from_be(addr.sin6_port);
+            let scope_id = *addr.u.sin6_scope_id();
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                port,
+                addr.sin6_flowinfo,
+                scope_id,
+            )))
+        }
+        _ => Err(io::ErrorKind::InvalidInput.into()),
+    }
+}
+
+fn set_opt(socket: TcpSocket, level: c_int, name: c_int, value: c_int) -> io::Result<()> {
+    syscall!(
+        setsockopt(
+            socket,
+            level,
+            name,
+            &value as *const c_int as *const c_char,
+            size_of::<c_int>() as c_int,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )
+    .map(|_| ())
+}
+
+fn get_opt(socket: TcpSocket, level: c_int, name: c_int) -> io::Result<c_int> {
+    let mut value: c_int = 0;
+    let mut length = size_of::<c_int>() as c_int;
+    syscall!(
+        getsockopt(
+            socket,
+            level,
+            name,
+            &mut value as *mut c_int as *mut c_char,
+            &mut length,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )
+    .map(|_| value)
 }
 
 pub fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
@@ -1,3 +1,5 @@
+use std::{io, mem};
+
 /// Associates readiness events with [`event::Source`]s.
 ///
 /// `Token` is a wrapper around `usize` and is used as an argument to
@@ -10,6 +12,19 @@
 /// [`Registry::register`]: struct.Registry.html#method.register
 /// [`Registry::reregister`]: struct.Registry.html#method.reregister
 ///
+/// Instead of picking tokens manually they can also be allocated using
+/// [`Registry::allocate_token`] (or [`Registry::register_allocated`]). Such
+/// tokens consist of an [`index`], which can be used to index a slab or
+/// vector, and a [`generation`], which is incremented each time the index is
+/// reused. This allows events for event sources that were deregistered to be
+/// detected, see [`Registry::is_token_allocated`].
+///
+/// [`Registry::allocate_token`]: struct.Registry.html#method.allocate_token
+/// [`Registry::register_allocated`]: struct.Registry.html#method.register_allocated
+/// [`index`]: #method.index
+/// [`generation`]: #method.generation
+/// [`Registry::is_token_allocated`]: struct.Registry.html#method.is_token_allocated
+///
 /// # Example
 ///
 /// Using `Token` to track which socket generated the event. In this example,
@@ -135,3 +150,97 @@ impl From<Token> for usize {
         val.0
     }
 }
+
+/// Number of bits of an allocated token used for the index, the remaining
+/// bits are used for the generation.
+const INDEX_BITS: usize = mem::size_of::<usize>() * 8 / 2;
+const INDEX_MASK: usize = (1 << INDEX_BITS) - 1;
+const MAX_GENERATION: usize = usize::max_value() >> INDEX_BITS;
+
+impl Token {
+    /// Returns the index of a token allocated by [`Registry::allocate_token`].
+    ///
+    /// The index is always smaller than the number of allocated tokens and is
+    /// reused once the token is released.
+    ///
+    /// [`Registry::allocate_token`]: struct.Registry.html#method.allocate_token
+    pub const fn index(self) -> usize {
+        self.0 & INDEX_MASK
+    }
+
+    /// Returns the generation of a token allocated by
+    /// [`Registry::allocate_token`].
+    ///
+    /// The generation of allocated tokens is never zero, so tokens smaller
+    /// than `2^(usize::BITS / 2)` never collide with allocated tokens.
+    ///
+    /// [`Registry::allocate_token`]: struct.Registry.html#method.allocate_token
+    pub const fn generation(self) -> usize {
+        self.0 >> INDEX_BITS
+    }
+}
+
+/// Allocator for tokens with a generation, see `Registry::allocate_token`.
+#[derive(Debug, Default)]
+pub(crate) struct TokenAllocator {
+    slots: Vec<Slot>,
+    /// Indices of the free slots.
+    free: Vec<usize>,
+}
+
+#[derive(Debug)]
+struct Slot {
+    /// Generation of the current, or next if not allocated, token.
+    generation: usize,
+    allocated: bool,
+}
+
+impl TokenAllocator {
+    pub(crate) fn allocate(&mut self) -> io::Result<Token> {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None if self.slots.len() <= INDEX_MASK => {
+                self.slots.push(Slot {
+                    generation: 1,
+                    allocated: false,
+                });
+                self.slots.len() - 1
+            }
+            None => {
+                // `io::Error::other` requires Rust 1.74.
+                #[allow(clippy::io_other_error)]
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "no more tokens available",
+                ));
+            }
+        };
+        let slot = &mut self.slots[index];
+        slot.allocated = true;
+        Ok(Token((slot.generation << INDEX_BITS) | index))
+    }
+
+    pub(crate) fn release(&mut self, token: Token) -> bool {
+        if !self.is_allocated(token) {
+            return false;
+        }
+
+        let index = token.index();
+        let slot = &mut self.slots[index];
+        slot.allocated = false;
+        slot.generation = if slot.generation == MAX_GENERATION {
+            1
+        } else {
+            slot.generation + 1
+        };
+        self.free.push(index);
+        true
+    }
+
+    pub(crate) fn is_allocated(&self, token: Token) -> bool {
+        match self.slots.get(token.index()) {
+            Some(slot) => slot.allocated && slot.generation == token.generation(),
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+#![cfg(all(
+    feature = "os-poll",
+    feature = "udp",
+    any(target_os = "android", target_os = "linux")
+))]
+
+use mio::net::{IcmpEcho, IcmpEchoKind, IcmpSocket};
+use mio::{Interest, Token};
+use std::io;
+use std::net::SocketAddr;
+
+#[macro_use]
+mod util;
+use util::{
+    any_local_address, any_local_ipv6_address, assert_send, assert_socket_close_on_exec,
+    assert_socket_non_blocking, assert_sync, assert_would_block, expect_events, init_with_poll,
+    ExpectEvent,
+};
+
+const DATA1: &[u8] = b"Hello same host!";
+const DEFAULT_BUF_SIZE: usize = 64;
+const ID1: Token = Token(0);
+
+#[test]
+fn icmp_socket_send_and_sync() {
+    assert_send::<IcmpSocket>();
+    assert_sync::<IcmpSocket>();
+}
+
+#[test]
+fn icmp_echo_v4() {
+    let echo = IcmpEcho {
+        kind: IcmpEchoKind::Request,
+        identifier: 0x1234,
+        sequence: 1,
+        payload: b"abc",
+    };
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let n = echo.encode_v4(&mut buf).unwrap();
+    assert_eq!(n, echo.encoded_len());
+    assert_eq!(
+        &buf[..n],
+        &[8, 0, 0x21, 0x68, 0x12, 0x34, 0, 1, b'a', b'b', b'c']
+    );
+    assert_eq!(IcmpEcho::decode_v4(&buf[..n]).unwrap(), echo);
+
+    let reply = IcmpEcho {
+        kind: IcmpEchoKind::Reply,
+        ..echo
+    };
+    let n = reply.encode_v4(&mut buf).unwrap();
+    assert_eq!(buf[0], 0);
+    assert_eq!(IcmpEcho::decode_v4(&buf[..n]).unwrap(), reply);
+
+    assert_eq!(
+        echo.encode_v4(&mut buf[..n - 1]).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    // Too short.
+    assert_eq!(
+        IcmpEcho::decode_v4(&buf[..7]).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    // Not an echo message, but a destination unreachable.
+    buf[0] = 3;
+    assert_eq!(
+        IcmpEcho::decode_v4(&buf[..n]).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn icmp_echo_v6() {
+    let echo = IcmpEcho::request(0x0102, DATA1);
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let n = echo.encode_v6(&mut buf).unwrap();
+    assert_eq!(&buf[..8], &[128, 0, 0, 0, 0, 0, 1, 2]);
+    assert_eq!(&buf[8..n], DATA1);
+    assert_eq!(IcmpEcho::decode_v6(&buf[..n]).unwrap(), echo);
+    // ICMP and ICMPv6 use different message types.
+    assert!(IcmpEcho::decode_v4(&buf[..n]).is_err());
+
+    buf[0] = 129;
+    let reply = IcmpEcho::decode_v6(&buf[..n]).unwrap();
+    assert_eq!(reply.kind, IcmpEchoKind::Reply);
+    assert_eq!(reply.sequence, 0x0102);
+}
+
+// Ping sockets must be allowed by `net.ipv4.ping_group_range`, which they
+// aren't by default on most systems.
+#[test]
+#[ignore = "requires ping sockets to be allowed by net.ipv4.ping_group_range"]
+fn icmp_socket_v4() {
+    icmp_socket_ping(
+        any_local_address(),
+        |echo, buf| echo.encode_v4(buf),
+        |packet| IcmpEcho::decode_v4(packet),
+    )
+}
+
+#[test]
+#[ignore = "requires ping sockets to be allowed by net.ipv4.ping_group_range"]
+fn icmp_socket_v6() {
+    icmp_socket_ping(
+        any_local_ipv6_address(),
+        |echo, buf| echo.encode_v6(buf),
+        |packet| IcmpEcho::decode_v6(packet),
+    )
+}
+
+fn icmp_socket_ping<E, D>(address: SocketAddr, encode: E, decode: D)
+where
+    E: Fn(&IcmpEcho<'_>, &mut [u8]) -> io::Result<usize>,
+    D: for<'a> Fn(&'a [u8]) -> io::Result<IcmpEcho<'a>>,
+{
+    let mut socket = IcmpSocket::bind(address).unwrap();
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+    let identifier = socket.local_addr().unwrap().port();
+    assert_ne!(identifier, 0);
+
+    let (mut poll, mut events) = init_with_poll();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(socket.recv_from(&mut buf));
+
+    let n = encode(&IcmpEcho::request(7, DATA1), &mut buf).unwrap();
+    let target = address;
+    assert_eq!(socket.send_to(&buf[..n], target).unwrap(), n);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, source) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(source, target);
+    let reply = decode(&buf[..n]).unwrap();
+    assert_eq!(reply.kind, IcmpEchoKind::Reply);
+    // The kernel sets the identifier to the socket's port.
+    assert_eq!(reply.identifier, identifier);
+    assert_eq!(reply.sequence, 7);
+    assert_eq!(reply.payload, DATA1);
+
+    assert_would_block(socket.recv_from(&mut buf));
+    assert!(socket.take_error().unwrap().is_none());
+}
@@ -8,6 +8,12 @@ fn is_tests() {
     assert!(Interest::WRITABLE.is_writable());
     assert!(!Interest::WRITABLE.is_aio());
     assert!(!Interest::WRITABLE.is_lio());
+    assert!(!Interest::READABLE.is_oneshot());
+    assert!(Interest::ONESHOT.is_oneshot());
+    assert!(!Interest::ONESHOT.is_readable());
+    assert!(!Interest::READABLE.is_level());
+    assert!(Interest::LEVEL.is_level());
+    assert!(!Interest::LEVEL.is_oneshot());
 }
 
 #[test]
@@ -25,6 +31,14 @@ fn fmt_debug() {
         format!("{:?}", Interest::READABLE | Interest::WRITABLE),
         "READABLE | WRITABLE"
     );
+    assert_eq!(
+        format!("{:?}", Interest::READABLE | Interest::ONESHOT),
+        "READABLE | ONESHOT"
+    );
+    assert_eq!(
+        format!("{:?}", Interest::WRITABLE | Interest::LEVEL),
+        "WRITABLE | LEVEL"
+    );
     #[cfg(any(
         target_os = "dragonfly",
         target_os = "freebsd",
@@ -6,7 +6,7 @@ use mio::{event, Events, Interest, Poll, Registry, Token};
 use std::net;
 use std::sync::{Arc, Barrier};
 use std::thread::{self, sleep};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 mod util;
@@ -95,6 +95,28 @@ fn zero_duration_polls_events() {
     drop(listener);
 }
 
+#[test]
+fn sub_millisecond_timeout() {
+    let (mut poll, mut events) = init_with_poll();
+
+    for timeout in &[
+        Duration::from_micros(200),
+        Duration::from_nanos(1_500_000),
+        Duration::from_nanos(1),
+    ] {
+        let start = Instant::now();
+        poll.poll(&mut events, Some(*timeout)).unwrap();
+        assert!(events.is_empty());
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= *timeout,
+            "poll returned before timeout: {:?} < {:?}",
+            elapsed,
+            timeout
+        );
+    }
+}
+
 #[test]
 fn poll_closes_fd() {
     init();
@@ -3,17 +3,30 @@
 use log::{debug, info, trace};
 #[cfg(debug_assertions)]
 use mio::net::UdpSocket;
+#[cfg(unix)]
+use mio::net::UnixStream;
 use mio::net::{TcpListener, TcpStream};
 use mio::{Events, Interest, Poll, Registry, Token};
+#[cfg(unix)]
+use std::io::Read;
 use std::io::{self, Write};
 use std::thread::sleep;
 use std::time::Duration;
 
+#[macro_use]
 mod util;
 use util::{any_local_address, assert_error, init};
+#[cfg(unix)]
+use util::{assert_would_block, expect_events, expect_no_events, init_with_poll, ExpectEvent};
 
 const SERVER: Token = Token(0);
 const CLIENT: Token = Token(1);
+#[cfg(unix)]
+const ID1: Token = Token(2);
+#[cfg(unix)]
+const ID2: Token = Token(3);
+#[cfg(unix)]
+const DATA: &[u8] = b"Hello oneshot!";
 
 struct TestHandler {
     server: TcpListener,
@@ -240,3 +253,245 @@ fn registering_after_deregistering() {
         .unwrap();
     assert!(events.is_empty());
 }
+
+#[test]
+#[cfg(unix)]
+fn oneshot_registration() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut stream1, mut stream2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut stream1, ID1, Interest::READABLE | Interest::ONESHOT)
+        .unwrap();
+
+    checked_write!(stream2.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    // The registration is disarmed after the first event, even though the data
+    // is not read and more data becomes available.
+    checked_write!(stream2.write(DATA));
+    expect_no_events(&mut poll, &mut events);
+
+    // Rearming will return an event as the stream is still readable.
+    poll.registry()
+        .rearm(&mut stream1, ID1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_no_events(&mut poll, &mut events);
+
+    let mut buf = [0; 32];
+    expect_read!(stream1.read(&mut buf[..DATA.len()]), DATA);
+    expect_read!(stream1.read(&mut buf[..DATA.len()]), DATA);
+    assert_would_block(stream1.read(&mut buf));
+
+    // Rearming with a different token.
+    poll.registry()
+        .rearm(&mut stream1, ID2, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+    checked_write!(stream2.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    checked_write!(stream2.write(DATA));
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg(unix)]
+fn level_triggered_registration() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut stream1, mut stream2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut stream1, ID1, Interest::READABLE | Interest::LEVEL)
+        .unwrap();
+
+    checked_write!(stream2.write(DATA));
+    checked_write!(stream2.write(DATA));
+
+    // As long as the data isn't read the stream stays readable, thus we keep
+    // getting events.
+    let mut buf = [0; 32];
+    for _ in 0..2 {
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+        expect_read!(stream1.read(&mut buf[..DATA.len()]), DATA);
+    }
+
+    // Once all data is read the stream is no longer ready.
+    expect_no_events(&mut poll, &mut events);
+
+    // Switching back to edge-triggered only returns a single event.
+    poll.registry()
+        .reregister(&mut stream1, ID1, Interest::READABLE)
+        .unwrap();
+    checked_write!(stream2.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_no_events(&mut poll, &mut events);
+
+    // And back to level-triggered.
+    poll.registry()
+        .reregister(&mut stream1, ID2, Interest::READABLE | Interest::LEVEL)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    expect_read!(stream1.read(&mut buf[..DATA.len()]), DATA);
+    assert_would_block(stream1.read(&mut buf));
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg(unix)]
+fn level_triggered_oneshot_registration() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut stream1, mut stream2) = UnixStream::pair().unwrap();
+    let interests = Interest::READABLE | Interest::LEVEL | Interest::ONESHOT;
+    poll.registry()
+        .register(&mut stream1, ID1, interests)
+        .unwrap();
+
+    checked_write!(stream2.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_no_events(&mut poll, &mut events);
+
+    // Still readable, so rearming returns another event.
+    poll.registry()
+        .rearm(&mut stream1, ID1, Interest::READABLE | Interest::LEVEL)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+fn allocate_token() {
+    init();
+    let poll = Poll::new().unwrap();
+    let registry = poll.registry();
+
+    let token1 = registry.allocate_token().unwrap();
+    let token2 = registry.allocate_token().unwrap();
+    assert_ne!(token1, token2);
+    assert_eq!(token1.index(), 0);
+    assert_eq!(token2.index(), 1);
+    // Never collides with small, manually picked tokens.
+    assert_ne!(token1.generation(), 0);
+    assert!(registry.is_token_allocated(token1));
+    assert!(!registry.is_token_allocated(Token(token1.index())));
+
+    assert!(registry.release_token(token1));
+    assert!(!registry.is_token_allocated(token1));
+    assert!(!registry.release_token(token1));
+
+    // The index is reused, but with a new generation.
+    let token3 = registry.allocate_token().unwrap();
+    assert_eq!(token3.index(), token1.index());
+    assert_ne!(token3.generation(), token1.generation());
+    assert!(!registry.is_token_allocated(token1));
+    assert!(registry.is_token_allocated(token3));
+
+    // Clones share the allocated tokens.
+    let registry2 = registry.try_clone().unwrap();
+    assert!(registry2.is_token_allocated(token2));
+    let token4 = registry2.allocate_token().unwrap();
+    assert_eq!(token4.index(), 2);
+    assert!(registry.release_token(token4));
+}
+
+#[test]
+#[cfg(unix)]
+fn register_allocated() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut stream1, mut stream2) = UnixStream::pair().unwrap();
+    let (mut stream3, _stream4) = UnixStream::pair().unwrap();
+    let token1 = poll
+        .registry()
+        .register_allocated(&mut stream1, Interest::READABLE)
+        .unwrap();
+
+    checked_write!(stream2.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(token1, Interest::READABLE)],
+    );
+
+    // Events returned for `stream1` after it's deregistered, and its index
+    // reused for `stream3`, can be detected as stale.
+    poll.registry()
+        .deregister_allocated(&mut stream1, token1)
+        .unwrap();
+    let token3 = poll
+        .registry()
+        .register_allocated(&mut stream3, Interest::WRITABLE)
+        .unwrap();
+    assert_eq!(token1.index(), token3.index());
+    assert!(!poll.registry().is_token_allocated(token1));
+    assert!(poll.registry().is_token_allocated(token3));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(token3, Interest::WRITABLE)],
+    );
+
+    // Registering the same source twice fails, which releases the token.
+    assert!(poll
+        .registry()
+        .register_allocated(&mut stream3, Interest::WRITABLE)
+        .is_err());
+    let token = poll.registry().allocate_token().unwrap();
+    assert_eq!(token.index(), token3.index() + 1);
+    assert_eq!(token.generation(), 2);
+
+    // Reregistering with a new allocated token, `deregister_allocated`
+    // releases the token passed to it.
+    poll.registry()
+        .reregister(&mut stream3, token, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .deregister_allocated(&mut stream3, token)
+        .unwrap();
+    assert!(!poll.registry().is_token_allocated(token));
+    assert!(poll.registry().is_token_allocated(token3));
+    assert!(poll.registry().release_token(token3));
+}
@@ -0,0 +1,118 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "tcp",
+    feature = "udp"
+))]
+
+use mio::net::{ReusePortBuilder, TcpStream, UdpSocket};
+use mio::{Interest, Token};
+use std::io;
+use std::mem::size_of;
+use std::os::unix::io::AsRawFd;
+
+mod util;
+use util::{
+    any_local_address, assert_socket_close_on_exec, assert_socket_non_blocking, init,
+    init_with_poll,
+};
+
+fn get_opt<S: AsRawFd>(socket: &S, name: libc::c_int) -> libc::c_int {
+    let mut value: libc::c_int = 0;
+    let mut length = size_of::<libc::c_int>() as libc::socklen_t;
+    let res = unsafe {
+        libc::getsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            name,
+            &mut value as *mut libc::c_int as *mut libc::c_void,
+            &mut length,
+        )
+    };
+    assert_eq!(res, 0, "getsockopt: {}", io::Error::last_os_error());
+    value
+}
+
+// `SO_INCOMING_CPU` from `asm/socket.h`.
+const SO_INCOMING_CPU: libc::c_int = 49;
+
+#[test]
+fn empty_group() {
+    init();
+
+    let err = ReusePortBuilder::new(0)
+        .bind_tcp(any_local_address())
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn tcp_group() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let group = ReusePortBuilder::new(3)
+        .backlog(16)
+        .bind_tcp(any_local_address())
+        .unwrap();
+    let addr = group.local_addr();
+    assert_ne!(addr.port(), 0);
+    assert_eq!(group.len(), 3);
+
+    let mut members = group.into_members();
+    for (n, member) in members.iter_mut().enumerate() {
+        assert_eq!(member.cpu(), None);
+        assert_eq!(member.socket().local_addr().unwrap(), addr);
+        assert_eq!(get_opt(member.socket(), libc::SO_REUSEPORT), 1);
+        assert_socket_non_blocking(member.socket());
+        assert_socket_close_on_exec(member.socket());
+        poll.registry()
+            .register(member.socket_mut(), Token(n), Interest::READABLE)
+            .unwrap();
+    }
+
+    // The connection is accepted by exactly one of the listeners.
+    let _stream = TcpStream::connect(addr).unwrap();
+    poll.poll(&mut events, None).unwrap();
+    let accepted = members
+        .iter()
+        .filter(|member| member.socket().accept().is_ok())
+        .count();
+    assert_eq!(accepted, 1);
+}
+
+#[test]
+fn udp_group_steer_by_cpu() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let group = ReusePortBuilder::new(2)
+        .steer_by_cpu(true)
+        .bind_udp(any_local_address())
+        .unwrap();
+    let addr = group.local_addr();
+
+    for (n, member) in group.iter().enumerate() {
+        assert_eq!(member.cpu(), Some(n));
+        assert_eq!(get_opt(member.socket(), SO_INCOMING_CPU), n as libc::c_int);
+        assert_eq!(member.socket().local_addr().unwrap(), addr);
+    }
+
+    let mut members = group.into_members();
+    for (n, member) in members.iter_mut().enumerate() {
+        poll.registry()
+            .register(member.socket_mut(), Token(n), Interest::READABLE)
+            .unwrap();
+    }
+
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    sender.send_to(b"hello", addr).unwrap();
+
+    // Every datagram goes to one socket, selected by the steering program.
+    poll.poll(&mut events, None).unwrap();
+    let mut buf = [0; 16];
+    let received: Vec<_> = members
+        .iter()
+        .filter_map(|member| member.socket().recv_from(&mut buf).ok())
+        .collect();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, 5);
+}
@@ -8,6 +8,8 @@ use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::sync::{Arc, Barrier};
 use std::thread;
+#[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+use std::time::Duration;
 
 mod util;
 use util::{
@@ -103,6 +105,69 @@ fn set_get_ttl() {
     assert!(listener.take_error().unwrap().is_none());
 }
 
+#[test]
+fn keepalive_inherited_by_accepted_streams() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    listener.set_keepalive(true).unwrap();
+    assert!(listener.keepalive().unwrap());
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    {
+        listener
+            .set_keepalive_time(Duration::from_secs(60))
+            .unwrap();
+        listener
+            .set_keepalive_interval(Duration::from_secs(10))
+            .unwrap();
+        listener.set_keepalive_retries(4).unwrap();
+    }
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    listener
+        .set_user_timeout(Some(Duration::from_secs(20)))
+        .unwrap();
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    let _client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (stream, _) = listener.accept().unwrap();
+    assert!(stream.keepalive().unwrap());
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    {
+        assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(60));
+        assert_eq!(
+            stream.keepalive_interval().unwrap(),
+            Duration::from_secs(10)
+        );
+        assert_eq!(stream.keepalive_retries().unwrap(), 4);
+    }
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(
+        stream.user_timeout().unwrap(),
+        Some(Duration::from_secs(20))
+    );
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_fastopen() {
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 0);
+    listener.set_fastopen(16).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 16);
+    listener.set_fastopen(0).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 0);
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     init();
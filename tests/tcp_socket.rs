@@ -0,0 +1,131 @@
+#![cfg(all(feature = "os-poll", feature = "tcp"))]
+
+use mio::net::TcpSocket;
+use mio::{Interest, Token};
+use std::io::{Read, Write};
+use std::net;
+
+mod util;
+use util::{
+    any_local_address, any_local_ipv6_address, assert_send, assert_socket_close_on_exec,
+    assert_socket_non_blocking, assert_sync, expect_events, init, init_with_poll, ExpectEvent,
+    Readiness,
+};
+
+const LISTENER: Token = Token(0);
+const STREAM: Token = Token(1);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<TcpSocket>();
+    assert_sync::<TcpSocket>();
+}
+
+#[test]
+fn set_reuseaddr() {
+    init();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    assert!(socket.get_reuseaddr().unwrap());
+    socket.set_reuseaddr(false).unwrap();
+    assert!(!socket.get_reuseaddr().unwrap());
+}
+
+#[test]
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+fn set_reuseport() {
+    init();
+
+    let socket = TcpSocket::new_v6().unwrap();
+    socket.set_reuseport(true).unwrap();
+    assert!(socket.get_reuseport().unwrap());
+}
+
+#[test]
+fn set_buffer_sizes() {
+    init();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_send_buffer_size(16 * 1024).unwrap();
+    // The operating system may adjust (e.g. double) the requested size.
+    assert!(socket.get_send_buffer_size().unwrap() >= 16 * 1024);
+    socket.set_recv_buffer_size(16 * 1024).unwrap();
+    assert!(socket.get_recv_buffer_size().unwrap() >= 16 * 1024);
+}
+
+#[test]
+fn get_localaddr() {
+    init();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let addr = socket.get_localaddr().unwrap();
+    assert_eq!(addr.ip(), any_local_address().ip());
+    assert_ne!(addr.port(), 0);
+}
+
+#[test]
+fn listen_and_connect() {
+    smoke_test(TcpSocket::new_v4, any_local_address());
+}
+
+#[test]
+fn listen_and_connect_ipv6() {
+    smoke_test(TcpSocket::new_v6, any_local_ipv6_address());
+}
+
+fn smoke_test<F>(new_socket: F, addr: net::SocketAddr)
+where
+    F: Fn() -> std::io::Result<TcpSocket>,
+{
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket = new_socket().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    socket.bind(addr).unwrap();
+    let mut listener = socket.listen(16).unwrap();
+    let listener_addr = listener.local_addr().unwrap();
+    assert_socket_non_blocking(&listener);
+    assert_socket_close_on_exec(&listener);
+
+    // Bind the outgoing connection to a known local address.
+    let socket = new_socket().unwrap();
+    socket.bind(addr).unwrap();
+    let local_addr = socket.get_localaddr().unwrap();
+    let mut stream = socket.connect(listener_addr).unwrap();
+    assert_socket_non_blocking(&stream);
+    assert_socket_close_on_exec(&stream);
+
+    poll.registry()
+        .register(&mut listener, LISTENER, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stream, STREAM, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(LISTENER, Readiness::READABLE),
+            ExpectEvent::new(STREAM, Readiness::WRITABLE),
+        ],
+    );
+
+    assert_eq!(stream.local_addr().unwrap(), local_addr);
+    let (mut accepted, peer_addr) = listener.accept().unwrap();
+    assert_eq!(peer_addr, local_addr);
+
+    stream.write_all(b"hello").unwrap();
+    poll.registry()
+        .register(&mut accepted, Token(2), Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(Token(2), Readiness::READABLE)],
+    );
+    let mut buf = [0; 16];
+    assert_eq!(accepted.read(&mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], b"hello");
+}
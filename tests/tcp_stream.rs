@@ -1,14 +1,16 @@
 #![cfg(all(feature = "os-poll", feature = "tcp"))]
 
-use mio::net::TcpStream;
-use mio::{Interest, Token};
+use mio::net::{ConnectStatus, TcpStream};
+use mio::{Events, Interest, Poll, Token};
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::{self, Shutdown, SocketAddr};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::sync::{mpsc::channel, Arc, Barrier};
 use std::thread;
-use std::time::Duration;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 #[macro_use]
 mod util;
@@ -164,6 +166,130 @@ fn set_get_ttl() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn timestamps() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE | Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(!stream.recv_timestamps().unwrap());
+    stream.set_recv_timestamps(true).unwrap();
+    assert!(stream.recv_timestamps().unwrap());
+
+    // The kernel enables timestamping asynchronously if no other socket uses
+    // it, so the first segments may not have a timestamp.
+    let mut attempts = 0;
+    let time = loop {
+        peer.write_all(DATA1).unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+
+        let mut buf = [0; 20];
+        let (n, time) = stream.recv_timestamped(&mut buf).unwrap();
+        assert_eq!(&buf[..n], DATA1);
+        if let Some(time) = time {
+            break time;
+        }
+        attempts += 1;
+        assert!(attempts < 10, "missing timestamp");
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert!(time <= SystemTime::now());
+
+    assert!(!stream.tx_timestamps().unwrap());
+    stream.set_tx_timestamps(true).unwrap();
+    assert!(stream.tx_timestamps().unwrap());
+    assert_would_block(stream.recv_tx_timestamp());
+
+    stream.write_all(DATA2).unwrap();
+    // The timestamp is queued on the error queue, which is reported as an
+    // error event.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let timestamp = loop {
+        match stream.recv_tx_timestamp() {
+            Ok(timestamp) => break timestamp,
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+        let now = Instant::now();
+        assert!(now < deadline, "missing transmit timestamp");
+        poll.poll(&mut events, Some(deadline - now)).unwrap();
+    };
+    // The id is the offset of the last byte written.
+    assert_eq!(timestamp.id(), DATA2_LEN as u32 - 1);
+    assert!(timestamp.time() <= SystemTime::now());
+}
+
+#[test]
+fn set_get_keepalive() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(!stream.keepalive().unwrap());
+    stream.set_keepalive(true).unwrap();
+    assert!(stream.keepalive().unwrap());
+
+    #[cfg(not(any(target_os = "openbsd", target_os = "solaris", target_os = "illumos")))]
+    {
+        stream.set_keepalive_time(Duration::from_secs(30)).unwrap();
+        assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(30));
+        stream
+            .set_keepalive_interval(Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(stream.keepalive_interval().unwrap(), Duration::from_secs(5));
+        stream.set_keepalive_retries(3).unwrap();
+        assert_eq!(stream.keepalive_retries().unwrap(), 3);
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        assert_eq!(stream.user_timeout().unwrap(), None);
+        stream
+            .set_user_timeout(Some(Duration::from_millis(1500)))
+            .unwrap();
+        assert_eq!(
+            stream.user_timeout().unwrap(),
+            Some(Duration::from_millis(1500))
+        );
+        stream.set_user_timeout(None).unwrap();
+        assert_eq!(stream.user_timeout().unwrap(), None);
+    }
+
+    stream.set_keepalive(false).unwrap();
+    assert!(!stream.keepalive().unwrap());
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     let (mut poll, mut events) = init_with_poll();
@@ -685,6 +811,227 @@ fn tcp_shutdown_client_both_close_event() {
     handle.join().expect("failed to join thread");
 }
 
+#[test]
+fn connect_status() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    match wait_for_connect(&mut poll, &mut events, &stream) {
+        ConnectStatus::Connected => {}
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+
+    // The connection was established, so closing it doesn't change that.
+    let (server_stream, _) = listener.accept().unwrap();
+    drop(server_stream);
+    match stream.connect_status() {
+        ConnectStatus::Connected => {}
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+}
+
+#[test]
+fn connect_status_refused() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address nothing is listening on.
+    let address = net::TcpListener::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let mut stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        // Some platforms refuse local connections right away.
+        Err(err) => {
+            assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+            return;
+        }
+    };
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    match wait_for_connect(&mut poll, &mut events, &stream) {
+        ConnectStatus::Failed(err) => assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused),
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+
+    // The error was taken, but the connect must still be reported as failed.
+    #[cfg(unix)]
+    match stream.connect_status() {
+        ConnectStatus::Failed(_) => {}
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn connect_status_reset() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE.add(Interest::WRITABLE))
+        .unwrap();
+
+    match wait_for_connect(&mut poll, &mut events, &stream) {
+        ConnectStatus::Connected => {}
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+
+    // Closing a socket with unread data resets the connection.
+    let (server_stream, _) = listener.accept().unwrap();
+    checked_write!(stream.write(DATA1));
+    drop(server_stream);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::READ_CLOSED)],
+    );
+    match stream.connect_status() {
+        ConnectStatus::Failed(err) => assert_eq!(err.kind(), io::ErrorKind::ConnectionReset),
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn connect_status_timed_out() {
+    use mio::net::TcpSocket;
+    use std::mem::size_of;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // With a backlog of zero the accept queue is full after a single
+    // connection, after which the listener drops any SYN it receives.
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let listener = socket.listen(0).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let mut stream1 = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream1, ID1, Interest::WRITABLE)
+        .unwrap();
+    match wait_for_connect(&mut poll, &mut events, &stream1) {
+        ConnectStatus::Connected => {}
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+
+    // Give up after retransmitting the SYN once, which takes about three
+    // seconds.
+    let socket = TcpSocket::new_v4().unwrap();
+    let syn_count: libc::c_int = 1;
+    let res = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_SYNCNT,
+            &syn_count as *const libc::c_int as *const libc::c_void,
+            size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    assert_eq!(res, 0, "{}", io::Error::last_os_error());
+
+    let mut stream2 = socket.connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream2, ID2, Interest::WRITABLE)
+        .unwrap();
+    match stream2.connect_status() {
+        ConnectStatus::Pending => {}
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+
+    match wait_for_connect(&mut poll, &mut events, &stream2) {
+        ConnectStatus::Failed(err) => assert_eq!(err.kind(), io::ErrorKind::TimedOut),
+        status => panic!("unexpected connect status: {:?}", status),
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn connect_with_data() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = mio::net::TcpListener::bind(any_local_address()).unwrap();
+    listener.set_fastopen(16).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID2, Interest::READABLE)
+        .unwrap();
+
+    // Fast Open must be enabled for both clients and servers in the kernel
+    // for data to be sent with the SYN.
+    // `Option::is_some_and` requires Rust 1.70.
+    #[allow(clippy::unnecessary_map_or)]
+    let fastopen = std::fs::read_to_string("/proc/sys/net/ipv4/tcp_fastopen")
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .map_or(false, |value| value & 0x3 == 0x3);
+
+    // The first connection gets a Fast Open cookie (unless an earlier one is
+    // still cached), which the second one uses to send data with the SYN.
+    for attempt in 0..2 {
+        let (mut stream, n) = TcpStream::connect_with_data(address, DATA1).unwrap();
+        if attempt == 1 && fastopen {
+            assert_eq!(n, DATA1_LEN);
+        }
+
+        poll.registry()
+            .register(&mut stream, ID1, Interest::WRITABLE)
+            .unwrap();
+        match wait_for_connect(&mut poll, &mut events, &stream) {
+            ConnectStatus::Connected => {}
+            status => panic!("unexpected connect status: {:?}", status),
+        }
+        checked_write!(stream.write(&DATA1[n..]));
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let (server_stream, _) = loop {
+            match listener.accept() {
+                Ok(accepted) => break accepted,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    let now = Instant::now();
+                    assert!(now < deadline, "no connection accepted");
+                    poll.poll(&mut events, Some(deadline - now))
+                        .expect("poll failed");
+                }
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        };
+        let server_stream = unsafe { net::TcpStream::from_raw_fd(server_stream.into_raw_fd()) };
+        server_stream.set_nonblocking(false).unwrap();
+        let mut buf = [0; DATA1_LEN];
+        (&server_stream).read_exact(&mut buf).unwrap();
+        assert_eq!(buf, DATA1);
+
+        poll.registry().deregister(&mut stream).unwrap();
+    }
+}
+
+/// Polls until the connect of `stream` is no longer pending, panicking if
+/// that takes longer than ten seconds.
+fn wait_for_connect(poll: &mut Poll, events: &mut Events, stream: &TcpStream) -> ConnectStatus {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        match stream.connect_status() {
+            ConnectStatus::Pending => {}
+            status => return status,
+        }
+        let now = Instant::now();
+        assert!(now < deadline, "connect still pending");
+        poll.poll(events, Some(deadline - now))
+            .expect("poll failed");
+    }
+}
+
 /// Start a listener that accepts `n_connections` connections on the returned
 /// address. It echos back any data it reads from the connection before
 /// accepting another one.
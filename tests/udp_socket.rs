@@ -1,8 +1,14 @@
 #![cfg(all(feature = "os-poll", feature = "udp"))]
 
 use log::{debug, info};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use mio::net::PacketInfo;
 use mio::net::UdpSocket;
+#[cfg(unix)]
+use mio::net::{RecvMsg, SendMsg};
 use mio::{Events, Interest, Poll, Registry, Token};
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{self, IpAddr, SocketAddr};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
@@ -10,6 +16,8 @@ use std::str;
 use std::sync::{Arc, Barrier};
 use std::thread;
 use std::time::Duration;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::{Instant, SystemTime};
 
 #[macro_use]
 mod util;
@@ -124,6 +132,368 @@ fn smoke_test_unconnected_udp_socket(mut socket1: UdpSocket, mut socket2: UdpSoc
     assert!(socket2.take_error().unwrap().is_none());
 }
 
+#[test]
+#[cfg(unix)]
+fn unconnected_udp_socket_vectored() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf1 = [0; 5];
+    let mut buf2 = [0; 20];
+    {
+        let mut bufs = [IoSliceMut::new(&mut buf1), IoSliceMut::new(&mut buf2)];
+        assert_would_block(socket1.recv_from_vectored(&mut bufs));
+    }
+
+    let bufs = [IoSlice::new(&DATA1[..5]), IoSlice::new(&DATA1[5..])];
+    assert_eq!(
+        socket2.send_to_vectored(&bufs, address1).unwrap(),
+        DATA1.len()
+    );
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut bufs = [IoSliceMut::new(&mut buf1), IoSliceMut::new(&mut buf2)];
+    let (n, from) = socket1.recv_from_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(from, address2);
+    assert_eq!(&buf1, &DATA1[..5]);
+    assert_eq!(&buf2[..n - 5], &DATA1[5..]);
+}
+
+#[test]
+#[cfg(unix)]
+fn connected_udp_socket_vectored() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    socket1.connect(socket2.local_addr().unwrap()).unwrap();
+    socket2.connect(socket1.local_addr().unwrap()).unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+
+    let bufs = [IoSlice::new(&DATA2[..6]), IoSlice::new(&DATA2[6..])];
+    assert_eq!(socket2.send_vectored(&bufs).unwrap(), DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf1 = [0; 6];
+    let mut buf2 = [0; 20];
+    let mut bufs = [IoSliceMut::new(&mut buf1), IoSliceMut::new(&mut buf2)];
+    let n = socket1.recv_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA2.len());
+    assert_eq!(&buf1, &DATA2[..6]);
+    assert_eq!(&buf2[..n - 6], &DATA2[6..]);
+
+    let mut bufs = [IoSliceMut::new(&mut buf1)];
+    assert_would_block(socket1.recv_vectored(&mut bufs));
+}
+
+#[test]
+#[cfg(unix)]
+fn udp_socket_batch() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+
+    let msgs = [SendMsg::to(DATA1, address1), SendMsg::to(DATA2, address1)];
+    assert_eq!(socket2.send_batch(&msgs).unwrap(), 2);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut short_buf = [0; 4];
+    let mut buf = [0; 20];
+    let mut msgs = [RecvMsg::new(&mut short_buf), RecvMsg::new(&mut buf)];
+    assert_eq!(socket1.recv_batch(&mut msgs).unwrap(), 2);
+    assert_eq!(msgs[0].data(), &DATA1[..4]);
+    assert!(msgs[0].is_truncated());
+    assert_eq!(msgs[1].data(), DATA2);
+    assert!(!msgs[1].is_truncated());
+    assert_eq!(msgs[0].addr(), Some(&address2));
+    assert_eq!(msgs[1].addr(), Some(&address2));
+
+    assert_would_block(socket1.recv_batch(&mut msgs));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn udp_socket_gso_gro() {
+    if !UdpSocket::supports_gso() || !UdpSocket::supports_gro() {
+        return;
+    }
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    socket1.set_gro(true).unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Three segments: two of 8 bytes and a final one of 4 bytes.
+    let data: Vec<u8> = (0..20).collect();
+    assert_eq!(socket2.send_to_segmented(&data, 8, address1).unwrap(), 20);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    // Depending on the kernel the segments are received coalesced or as
+    // separate datagrams.
+    let mut received = Vec::new();
+    let mut buf = [0; 64];
+    while received.len() < data.len() {
+        let (n, addr, segment_size) = socket1.recv_from_gro(&mut buf).unwrap();
+        assert_eq!(addr, address2);
+        match segment_size {
+            Some(segment_size) => assert_eq!(segment_size, 8),
+            None => assert!(n <= 8),
+        }
+        received.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(received, data);
+
+    assert_would_block(socket1.recv_from_gro(&mut buf));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn udp_socket_pktinfo() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    assert!(!socket1.pktinfo_v4().unwrap());
+    socket1.set_pktinfo_v4(true).unwrap();
+    assert!(socket1.pktinfo_v4().unwrap());
+    socket2.set_pktinfo_v4(true).unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(socket2.send_to(DATA1, address1).unwrap(), DATA1.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let (n, addr, info) = socket1.recv_msg(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(addr, address2);
+    let info = info.expect("missing packet info");
+    assert_eq!(info.addr(), address1.ip());
+    assert_ne!(info.interface(), 0);
+
+    // Reply using the received packet info.
+    assert_eq!(socket1.send_msg(DATA2, addr, &info).unwrap(), DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    let (n, addr, info) = socket2.recv_msg(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(addr, address1);
+    assert_eq!(info.map(|info| info.addr()), Some(address2.ip()));
+
+    // Without the option set no packet info is returned.
+    socket1.set_pktinfo_v4(false).unwrap();
+    let info = PacketInfo::new(address2.ip(), 0);
+    socket2.send_msg(DATA1, address1, &info).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (n, _, info) = socket1.recv_msg(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert!(info.is_none());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn udp_socket_pktinfo_v6() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    assert!(!socket1.recv_pktinfo_v6().unwrap());
+    socket1.set_recv_pktinfo_v6(true).unwrap();
+    assert!(socket1.recv_pktinfo_v6().unwrap());
+    socket2.set_recv_pktinfo_v6(true).unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(socket2.send_to(DATA1, address1).unwrap(), DATA1.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let (n, addr, info) = socket1.recv_msg(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(addr, address2);
+    let info = info.expect("missing packet info");
+    assert_eq!(info.addr(), address1.ip());
+    assert_ne!(info.interface(), 0);
+
+    // Reply using the received packet info.
+    assert_eq!(socket1.send_msg(DATA2, addr, &info).unwrap(), DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    let (n, addr, info) = socket2.recv_msg(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(addr, address1);
+    assert_eq!(info.map(|info| info.addr()), Some(address2.ip()));
+
+    // Without the option set no packet info is returned.
+    socket1.set_recv_pktinfo_v6(false).unwrap();
+    socket2.send_to(DATA1, address1).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (n, _, info) = socket1.recv_msg(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert!(info.is_none());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn udp_socket_timestamps() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    assert!(!socket1.recv_timestamps().unwrap());
+    socket1.set_recv_timestamps(true).unwrap();
+    assert!(socket1.recv_timestamps().unwrap());
+    assert!(!socket2.tx_timestamps().unwrap());
+    socket2.set_tx_timestamps(true).unwrap();
+    assert!(socket2.tx_timestamps().unwrap());
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    assert_would_block(socket2.recv_tx_timestamp());
+
+    // The kernel enables timestamping asynchronously if no other socket uses
+    // it, so the first datagrams may not have a timestamp.
+    let mut attempts = 0;
+    let time = loop {
+        assert_eq!(socket2.send_to(DATA1, address1).unwrap(), DATA1.len());
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+
+        let mut buf = [0; 20];
+        let (n, addr, time) = socket1.recv_from_timestamped(&mut buf).unwrap();
+        assert_eq!(&buf[..n], DATA1);
+        assert_eq!(addr, address2);
+        if let Some(time) = time {
+            break time;
+        }
+        attempts += 1;
+        assert!(attempts < 10, "missing timestamp");
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert!(time <= SystemTime::now());
+
+    // The transmit timestamp is queued on the error queue, which is reported
+    // as an error event.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let timestamp = loop {
+        match socket2.recv_tx_timestamp() {
+            Ok(timestamp) => break timestamp,
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+        let now = Instant::now();
+        assert!(now < deadline, "missing transmit timestamp");
+        poll.poll(&mut events, Some(deadline - now)).unwrap();
+    };
+    assert_eq!(timestamp.id(), 0);
+    assert!(timestamp.time() <= time);
+}
+
 #[test]
 fn set_get_ttl() {
     let socket1 = UdpSocket::bind(any_local_address()).unwrap();
@@ -1,9 +1,10 @@
 #![cfg(all(unix, feature = "os-poll", feature = "uds"))]
 
-use mio::net::UnixDatagram;
+use mio::net::{RecvMsg, SendMsg, SocketAddr, UnixDatagram};
 use mio::{Interest, Token};
 use std::io;
 use std::net::Shutdown;
+use std::os::unix::io::AsRawFd;
 use std::os::unix::net;
 
 #[macro_use]
@@ -165,6 +166,234 @@ fn unix_datagram_pair() {
     assert!(datagram2.take_error().unwrap().is_none());
 }
 
+#[test]
+fn unix_datagram_batch() {
+    let (mut poll, mut events) = init_with_poll();
+    let path1 = temp_file("unix_datagram_batch1");
+    let path2 = temp_file("unix_datagram_batch2");
+
+    let mut datagram1 = UnixDatagram::bind(&path1).unwrap();
+    let datagram2 = UnixDatagram::bind(&path2).unwrap();
+    poll.registry()
+        .register(&mut datagram1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let mut bufs = [[0; DEFAULT_BUF_SIZE]; 4];
+    {
+        let mut msgs: Vec<_> = bufs
+            .iter_mut()
+            .map(|buf| RecvMsg::new(&mut buf[..]))
+            .collect();
+        assert_would_block(datagram1.recv_batch(&mut msgs));
+    }
+
+    let msgs = [
+        SendMsg::to(DATA1, path1.as_path()),
+        SendMsg::to(DATA2, path1.as_path()),
+        SendMsg::to(&[], path1.as_path()),
+    ];
+    assert_eq!(datagram2.send_batch(&msgs).unwrap(), 3);
+    assert_eq!(datagram2.send_batch(&[]).unwrap(), 0);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut short_buf = [0; 4];
+    let mut msgs: Vec<_> = bufs
+        .iter_mut()
+        .map(|buf| RecvMsg::new(&mut buf[..]))
+        .collect();
+    msgs[0] = RecvMsg::new(&mut short_buf);
+    assert_eq!(datagram1.recv_batch(&mut msgs).unwrap(), 3);
+
+    assert_eq!(msgs[0].data(), &DATA1[..4]);
+    assert!(msgs[0].is_truncated());
+    assert_eq!(msgs[1].data(), DATA2);
+    assert!(!msgs[1].is_truncated());
+    assert!(msgs[2].is_empty());
+    for msg in &msgs[..3] {
+        assert_eq!(msg.addr().unwrap().as_pathname(), Some(path2.as_path()));
+    }
+    assert!(msgs[3].addr().is_none());
+
+    assert_would_block(datagram1.recv_batch(&mut msgs));
+}
+
+#[test]
+fn unix_datagram_batch_connected() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut datagram1, datagram2) = UnixDatagram::pair().unwrap();
+    poll.registry()
+        .register(&mut datagram1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let msgs = [SendMsg::new(DATA1), SendMsg::new(DATA2)];
+    assert_eq!(datagram2.send_batch(&msgs).unwrap(), 2);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut bufs = [[0; DEFAULT_BUF_SIZE]; 4];
+    let mut msgs: Vec<_> = bufs
+        .iter_mut()
+        .map(|buf| RecvMsg::new(&mut buf[..]))
+        .collect();
+    assert_eq!(datagram1.recv_batch(&mut msgs).unwrap(), 2);
+    assert_eq!(msgs[0].data(), DATA1);
+    assert_eq!(msgs[1].data(), DATA2);
+    assert!(msgs[0].addr().unwrap().is_unnamed());
+}
+
+#[test]
+fn unix_datagram_pass_fds() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut datagram1, datagram2) = UnixDatagram::pair().unwrap();
+    poll.registry()
+        .register(&mut datagram1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let (passed1, passed2) = net::UnixDatagram::pair().unwrap();
+    let fds = [passed1.as_raw_fd(), passed2.as_raw_fd()];
+    assert_eq!(datagram2.send_with_fds(DATA1, &fds).unwrap(), DATA1.len());
+    assert_eq!(datagram2.send_with_fds(DATA2, &fds).unwrap(), DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, fds) = datagram1.recv_with_fds(&mut buf, 2).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert!(!fds.is_truncated());
+    assert_eq!(fds.len(), 2);
+    for fd in &fds {
+        assert_socket_close_on_exec(fd);
+    }
+
+    let (n, fds) = datagram1.recv_with_fds(&mut buf, 1).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert!(fds.is_truncated());
+
+    assert_would_block(datagram1.recv_with_fds(&mut buf, 2));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_datagram_passcred() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut datagram1, datagram2) = UnixDatagram::pair().unwrap();
+    poll.registry()
+        .register(&mut datagram1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    datagram2.send(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    // Without `SO_PASSCRED` no credentials are returned.
+    assert!(!datagram1.passcred().unwrap());
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, _, cred) = datagram1.recv_from_with_cred(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert!(cred.is_none());
+
+    datagram1.set_passcred(true).unwrap();
+    assert!(datagram1.passcred().unwrap());
+    datagram2.send(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let (n, addr, cred) = datagram1.recv_from_with_cred(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert!(addr.is_unnamed());
+    let cred = cred.expect("missing credentials");
+    assert_eq!(cred.uid(), unsafe { libc::geteuid() });
+    assert_eq!(cred.gid(), unsafe { libc::getegid() });
+    assert_eq!(cred.pid(), Some(unsafe { libc::getpid() }));
+
+    assert_would_block(datagram1.recv_from_with_cred(&mut buf));
+}
+
+#[test]
+fn unix_datagram_bind_addr() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path1 = temp_file("unix_datagram_bind_addr1");
+    let path2 = temp_file("unix_datagram_bind_addr2");
+    let address1 = SocketAddr::from_pathname(&path1).unwrap();
+    let address2 = SocketAddr::from_pathname(&path2).unwrap();
+
+    let mut datagram1 = UnixDatagram::bind_addr(&address1).unwrap();
+    let datagram2 = UnixDatagram::bind_addr(&address2).unwrap();
+    poll.registry()
+        .register(&mut datagram1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    datagram2.send_to_addr(DATA1, &address1).unwrap();
+    datagram2.connect_addr(&address1).unwrap();
+    datagram2.send(DATA2).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, addr) = datagram1.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(addr.as_pathname(), Some(path2.as_path()));
+    let (n, _) = datagram1.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_datagram_abstract_addr() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let name = format!("mio-unix_datagram_abstract_addr-{}", std::process::id());
+    let address = SocketAddr::from_abstract_name(&name).unwrap();
+    let mut datagram1 = UnixDatagram::bind_addr(&address).unwrap();
+    let datagram2 = UnixDatagram::unbound().unwrap();
+    poll.registry()
+        .register(&mut datagram1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    datagram2.send_to_addr(DATA1, &address).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, _) = datagram1.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(
+        datagram1.local_addr().unwrap().as_abstract_name(),
+        Some(name.as_bytes())
+    );
+}
+
 #[test]
 fn unix_datagram_shutdown() {
     let (mut poll, mut events) = init_with_poll();
@@ -0,0 +1,178 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "os-util"
+))]
+
+use mio::unix::{Inotify, InotifyEvent, WatchEvent};
+use mio::{Interest, Token};
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+mod util;
+use util::{
+    assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
+    expect_events, expect_no_events, init, init_with_poll, temp_file, ExpectEvent, Readiness,
+};
+
+const TOKEN: Token = Token(0);
+
+#[test]
+fn inotify_send_and_sync() {
+    assert_send::<Inotify>();
+    assert_sync::<Inotify>();
+}
+
+#[test]
+fn inotify_flags() {
+    init();
+
+    let inotify = Inotify::new().unwrap();
+    assert_socket_non_blocking(&inotify);
+    assert_socket_close_on_exec(&inotify);
+}
+
+#[test]
+fn inotify_events() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let dir = create_dir(temp_file("inotify_events"));
+    let mut inotify = Inotify::new().unwrap();
+    let watch = inotify
+        .add_watch(&dir, libc::IN_CREATE | libc::IN_MODIFY | libc::IN_MOVE)
+        .unwrap();
+    poll.registry()
+        .register(&mut inotify, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    expect_no_events(&mut poll, &mut events);
+    assert!(inotify.read_events().unwrap().is_empty());
+
+    let path = dir.join("file");
+    let mut file = File::create(&path).unwrap();
+    file.write_all(b"Hello inotify").unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let watch_events = only_watch_events(inotify.read_events().unwrap());
+    assert_eq!(watch_events.len(), 2);
+    for (event, mask) in watch_events.iter().zip(&[libc::IN_CREATE, libc::IN_MODIFY]) {
+        assert_eq!(event.watch(), watch);
+        assert_eq!(event.mask(), *mask);
+        assert_eq!(event.cookie(), 0);
+        assert_eq!(event.name(), Some(OsStr::new("file")));
+        assert_eq!(event.path(), Some(&*path));
+    }
+
+    // Moves have a cookie to relate the events.
+    let new_path = dir.join("new_file");
+    fs::rename(&path, &new_path).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let watch_events = only_watch_events(inotify.read_events().unwrap());
+    assert_eq!(watch_events.len(), 2);
+    assert_eq!(watch_events[0].mask(), libc::IN_MOVED_FROM);
+    assert_eq!(watch_events[0].path(), Some(&*path));
+    assert_eq!(watch_events[1].mask(), libc::IN_MOVED_TO);
+    assert_eq!(watch_events[1].path(), Some(&*new_path));
+    assert_ne!(watch_events[0].cookie(), 0);
+    assert_eq!(watch_events[0].cookie(), watch_events[1].cookie());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn inotify_rm_watch() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let dir = create_dir(temp_file("inotify_rm_watch"));
+    let mut inotify = Inotify::new().unwrap();
+    let watch = inotify.add_watch(&dir, libc::IN_CREATE).unwrap();
+    poll.registry()
+        .register(&mut inotify, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    inotify.rm_watch(watch).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let watch_events = only_watch_events(inotify.read_events().unwrap());
+    assert_eq!(watch_events.len(), 1);
+    assert_eq!(watch_events[0].watch(), watch);
+    assert_eq!(watch_events[0].mask(), libc::IN_IGNORED);
+    assert_eq!(watch_events[0].name(), None);
+    assert_eq!(watch_events[0].path(), Some(&*dir));
+
+    // No more events for the removed watch.
+    File::create(dir.join("file")).unwrap();
+    expect_no_events(&mut poll, &mut events);
+    assert!(inotify.rm_watch(watch).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn inotify_overflow() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let max_queued_events: usize = fs::read_to_string("/proc/sys/fs/inotify/max_queued_events")
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+
+    let dir = create_dir(temp_file("inotify_overflow"));
+    let path = dir.join("file");
+    File::create(&path).unwrap();
+
+    let mut inotify = Inotify::new().unwrap();
+    inotify
+        .add_watch(&path, libc::IN_OPEN | libc::IN_CLOSE)
+        .unwrap();
+    poll.registry()
+        .register(&mut inotify, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    // Each iteration generates two events, which are not merged as they're
+    // different.
+    for _ in 0..(max_queued_events / 2) + 1 {
+        drop(File::open(&path).unwrap());
+    }
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+
+    let inotify_events = inotify.read_events().unwrap();
+    assert_eq!(inotify_events.len(), max_queued_events + 1);
+    assert_eq!(inotify_events.last(), Some(&InotifyEvent::Overflow));
+    assert_eq!(only_watch_events(inotify_events).len(), max_queued_events);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+fn create_dir(dir: PathBuf) -> PathBuf {
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn only_watch_events(events: Vec<InotifyEvent>) -> Vec<WatchEvent> {
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            InotifyEvent::Watch(event) => Some(event),
+            InotifyEvent::Overflow => None,
+        })
+        .collect()
+}
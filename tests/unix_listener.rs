@@ -1,6 +1,6 @@
 #![cfg(all(unix, feature = "os-poll", feature = "uds"))]
 
-use mio::net::UnixListener;
+use mio::net::{SocketAddr, UnixListener, UnixStream};
 use mio::{Interest, Token};
 use std::io::{self, Read};
 use std::os::unix::net;
@@ -74,6 +74,82 @@ fn unix_listener_local_addr() {
     handle.join().unwrap();
 }
 
+#[test]
+fn unix_listener_bind_addr() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_file("unix_listener_bind_addr");
+    let address = SocketAddr::from_pathname(&path).unwrap();
+    assert_eq!(address.as_pathname(), Some(path.as_path()));
+    assert!(!address.is_unnamed());
+
+    let mut listener = UnixListener::bind_addr(&address).unwrap();
+    poll.registry()
+        .register(&mut listener, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let _stream = UnixStream::connect_addr(&address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+    let (stream, _) = listener.accept().unwrap();
+    assert_eq!(
+        stream.local_addr().unwrap().as_pathname(),
+        Some(path.as_path())
+    );
+
+    // Pathnames starting with a null byte are abstract names.
+    assert!(SocketAddr::from_pathname("\0name").is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_listener_abstract_addr() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Include the process id so concurrent test runs don't collide.
+    let name = format!("mio-unix_listener_abstract_addr-{}", std::process::id());
+    let address = SocketAddr::from_abstract_name(&name).unwrap();
+    assert_eq!(address.as_abstract_name(), Some(name.as_bytes()));
+    assert!(address.as_pathname().is_none());
+    assert!(!address.is_unnamed());
+    assert_eq!(format!("{:?}", address), format!("{:?} (abstract)", name));
+
+    let mut listener = UnixListener::bind_addr(&address).unwrap();
+    let local_addr = listener.local_addr().unwrap();
+    assert_eq!(local_addr.as_abstract_name(), Some(name.as_bytes()));
+    // Binding the same abstract name twice fails.
+    assert!(UnixListener::bind_addr(&address).is_err());
+
+    poll.registry()
+        .register(&mut listener, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let stream = UnixStream::connect_addr(&address).unwrap();
+    assert_eq!(
+        stream.peer_addr().unwrap().as_abstract_name(),
+        Some(name.as_bytes())
+    );
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+    let (_stream, peer_addr) = listener.accept().unwrap();
+    assert!(peer_addr.is_unnamed());
+    assert_eq!(format!("{:?}", peer_addr), "(unnamed)");
+
+    // Abstract names may contain any bytes, which are escaped when
+    // formatting.
+    let address = SocketAddr::from_abstract_name(b"a\0b\n").unwrap();
+    assert_eq!(address.as_abstract_name(), Some(&b"a\0b\n"[..]));
+    assert_eq!(format!("{:?}", address), "\"a\\x00b\\n\" (abstract)");
+
+    assert!(SocketAddr::from_abstract_name(&[b'a'; 200][..]).is_err());
+}
+
 #[test]
 fn unix_listener_register() {
     let (mut poll, mut events) = init_with_poll();
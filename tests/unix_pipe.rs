@@ -0,0 +1,187 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-util"))]
+
+use mio::unix::pipe::{self, Receiver, Sender};
+use mio::{Interest, Token};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+
+mod util;
+use util::{
+    assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
+    assert_would_block, expect_events, expect_no_events, init, init_with_poll, ExpectEvent,
+    Readiness,
+};
+
+const RECEIVER: Token = Token(0);
+const SENDER: Token = Token(1);
+
+const DATA1: &[u8; 11] = b"Hello world";
+const DATA2: &[u8; 12] = b"Hello mars!!";
+
+#[test]
+fn pipe_send_and_sync() {
+    assert_send::<Sender>();
+    assert_sync::<Sender>();
+    assert_send::<Receiver>();
+    assert_sync::<Receiver>();
+}
+
+#[test]
+fn pipe_flags() {
+    init();
+
+    let (sender, receiver) = pipe::new().unwrap();
+    assert_socket_non_blocking(&sender);
+    assert_socket_close_on_exec(&sender);
+    assert_socket_non_blocking(&receiver);
+    assert_socket_close_on_exec(&receiver);
+}
+
+#[test]
+fn smoke() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, mut receiver) = pipe::new().unwrap();
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut sender, SENDER, Interest::WRITABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interest::WRITABLE)],
+    );
+
+    let mut buf = [0; 20];
+    assert_would_block(receiver.read(&mut buf));
+
+    assert_eq!(sender.write(DATA1).unwrap(), DATA1.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    assert_eq!(receiver.read(&mut buf).unwrap(), DATA1.len());
+    assert_eq!(&buf[..DATA1.len()], DATA1);
+
+    // Using references.
+    assert_eq!((&sender).write(DATA2).unwrap(), DATA2.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    assert_eq!((&receiver).read(&mut buf).unwrap(), DATA2.len());
+    assert_eq!(&buf[..DATA2.len()], DATA2);
+    assert_would_block(receiver.read(&mut buf));
+}
+
+#[test]
+fn event_when_sender_is_dropped() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, mut receiver) = pipe::new().unwrap();
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(sender.write(DATA1).unwrap(), DATA1.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+
+    drop(sender);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Readiness::READ_CLOSED)],
+    );
+
+    // The data written before the sender was dropped can still be read.
+    let mut buf = [0; 20];
+    assert_eq!(receiver.read(&mut buf).unwrap(), DATA1.len());
+    assert_eq!(receiver.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn event_when_receiver_is_dropped() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+    poll.registry()
+        .register(&mut sender, SENDER, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interest::WRITABLE)],
+    );
+
+    drop(receiver);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(
+            SENDER,
+            Readiness::WRITE_CLOSED | Readiness::ERROR,
+        )],
+    );
+}
+
+#[test]
+fn from_child_process_io() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // `cat` simply echos everything that we write via standard in.
+    let mut child = Command::new("cat")
+        .env_clear()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start `cat` command");
+
+    let mut sender = Sender::from(child.stdin.take().unwrap());
+    let mut receiver = Receiver::from(child.stdout.take().unwrap());
+    sender.set_nonblocking(true).unwrap();
+    receiver.set_nonblocking(true).unwrap();
+    assert_socket_non_blocking(&sender);
+    assert_socket_non_blocking(&receiver);
+
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut sender, SENDER, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interest::WRITABLE)],
+    );
+
+    assert_eq!(sender.write(DATA1).unwrap(), DATA1.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    assert_eq!(receiver.read(&mut buf).unwrap(), DATA1.len());
+    assert_eq!(&buf[..DATA1.len()], DATA1);
+
+    // Closing standard in stops `cat`.
+    drop(sender);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Readiness::READ_CLOSED)],
+    );
+    assert_eq!(receiver.read(&mut buf).unwrap(), 0);
+    assert!(child.wait().unwrap().success());
+    expect_no_events(&mut poll, &mut events);
+}
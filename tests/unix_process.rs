@@ -0,0 +1,115 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "os-util"
+))]
+
+use mio::unix::Process;
+use mio::{Interest, Token};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Stdio};
+
+mod util;
+use util::{
+    assert_send, assert_socket_close_on_exec, assert_sync, expect_events, expect_no_events, init,
+    init_with_poll, ExpectEvent, Readiness,
+};
+
+const TOKEN: Token = Token(0);
+
+#[test]
+fn process_send_and_sync() {
+    assert_send::<Process>();
+    assert_sync::<Process>();
+}
+
+#[test]
+fn process_flags() {
+    init();
+
+    let mut child = Command::new("true").spawn().unwrap();
+    let process = Process::from_child(&child).unwrap();
+    assert_eq!(process.pid(), child.id() as libc::pid_t);
+    assert_socket_close_on_exec(&process);
+    child.wait().unwrap();
+}
+
+#[test]
+fn process_exit() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut child = Command::new("cat").stdin(Stdio::piped()).spawn().unwrap();
+    let mut process = Process::from_child(&child).unwrap();
+    poll.registry()
+        .register(&mut process, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    expect_no_events(&mut poll, &mut events);
+    assert!(process.try_wait().unwrap().is_none());
+
+    // Closing standard in stops `cat`.
+    drop(child.stdin.take());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let status = process.try_wait().unwrap().unwrap();
+    assert!(status.success());
+
+    // The child is reaped, so waiting again fails.
+    assert!(process.try_wait().is_err());
+    assert!(child.wait().is_err());
+}
+
+#[test]
+fn process_exit_code() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Passing an array (rather than a slice) requires Rust 1.53.
+    let args: &[&str] = &["-c", "exit 3"];
+    let mut child = Command::new("sh").args(args).spawn().unwrap();
+    let mut process = Process::from_child(&child).unwrap();
+    poll.registry()
+        .register(&mut process, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let status = process.try_wait().unwrap().unwrap();
+    assert_eq!(status.code(), Some(3));
+    assert!(child.wait().is_err());
+}
+
+#[test]
+fn process_killed() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut child = Command::new("sleep").arg("60").spawn().unwrap();
+    let mut process = Process::new(child.id() as libc::pid_t).unwrap();
+    poll.registry()
+        .register(&mut process, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    child.kill().unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let status = process.try_wait().unwrap().unwrap();
+    assert_eq!(status.code(), None);
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+    assert!(child.wait().is_err());
+}
+
+#[test]
+fn process_not_found() {
+    init();
+
+    // Process ids are always positive.
+    assert!(Process::new(-1).is_err());
+}
@@ -0,0 +1,195 @@
+#![cfg(all(
+    unix,
+    feature = "os-poll",
+    feature = "uds",
+    not(any(target_os = "ios", target_os = "macos"))
+))]
+
+use mio::net::{UnixSeqpacket, UnixSeqpacketListener};
+use mio::{Interest, Token};
+use std::net::Shutdown;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+#[macro_use]
+mod util;
+use util::{
+    assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
+    assert_would_block, expect_events, expect_no_events, init_with_poll, temp_file, ExpectEvent,
+};
+
+const DATA1: &[u8] = b"Hello same host!";
+const DATA2: &[u8] = b"Why hello mio!";
+const DEFAULT_BUF_SIZE: usize = 64;
+const TOKEN_1: Token = Token(0);
+const TOKEN_2: Token = Token(1);
+const TOKEN_3: Token = Token(2);
+
+#[test]
+fn unix_seqpacket_send_and_sync() {
+    assert_send::<UnixSeqpacketListener>();
+    assert_sync::<UnixSeqpacketListener>();
+    assert_send::<UnixSeqpacket>();
+    assert_sync::<UnixSeqpacket>();
+}
+
+#[test]
+fn unix_seqpacket_listener() {
+    let (mut poll, mut events) = init_with_poll();
+    let path = temp_file("unix_seqpacket_listener");
+
+    let mut listener = UnixSeqpacketListener::bind(&path).unwrap();
+    assert_socket_non_blocking(&listener);
+    assert_socket_close_on_exec(&listener);
+    assert_eq!(
+        listener.local_addr().unwrap().as_pathname(),
+        Some(path.as_path())
+    );
+    assert_would_block(listener.accept());
+
+    poll.registry()
+        .register(&mut listener, TOKEN_1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    let mut client = UnixSeqpacket::connect(&path).unwrap();
+    assert_socket_non_blocking(&client);
+    assert_socket_close_on_exec(&client);
+    poll.registry()
+        .register(
+            &mut client,
+            TOKEN_2,
+            Interest::READABLE.add(Interest::WRITABLE),
+        )
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(TOKEN_1, Interest::READABLE),
+            ExpectEvent::new(TOKEN_2, Interest::WRITABLE),
+        ],
+    );
+
+    let (mut server, address) = listener.accept().unwrap();
+    assert!(address.is_unnamed());
+    assert_socket_non_blocking(&server);
+    assert_socket_close_on_exec(&server);
+    assert_would_block(listener.accept());
+    assert!(listener.take_error().unwrap().is_none());
+    assert_eq!(
+        client.peer_addr().unwrap().as_pathname(),
+        Some(path.as_path())
+    );
+    assert_eq!(
+        server.local_addr().unwrap().as_pathname(),
+        Some(path.as_path())
+    );
+
+    poll.registry()
+        .register(&mut server, TOKEN_3, Interest::READABLE)
+        .unwrap();
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(server.recv(&mut buf));
+
+    assert_eq!(client.send(DATA1).unwrap(), DATA1.len());
+    assert_eq!(client.send(DATA2).unwrap(), DATA2.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_3, Interest::READABLE)],
+    );
+
+    // Every send is received as a separate message.
+    assert_eq!(server.recv(&mut buf).unwrap(), (DATA1.len(), false));
+    assert_eq!(&buf[..DATA1.len()], DATA1);
+    assert_eq!(server.recv(&mut buf).unwrap(), (DATA2.len(), false));
+    assert_eq!(&buf[..DATA2.len()], DATA2);
+    assert_would_block(server.recv(&mut buf));
+
+    drop(client);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_3, Interest::READABLE)],
+    );
+    assert_eq!(server.recv(&mut buf).unwrap(), (0, false));
+}
+
+#[test]
+fn unix_seqpacket_pair() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut socket1, socket2) = UnixSeqpacket::pair().unwrap();
+    assert_socket_non_blocking(&socket1);
+    assert_socket_close_on_exec(&socket1);
+    assert!(socket1.local_addr().unwrap().is_unnamed());
+    assert!(socket1.peer_addr().unwrap().is_unnamed());
+
+    poll.registry()
+        .register(&mut socket1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    socket2.send(DATA1).unwrap();
+    socket2.send(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    // The rest of a truncated message is discarded.
+    let mut buf = [0; 4];
+    assert_eq!(socket1.recv(&mut buf).unwrap(), (4, true));
+    assert_eq!(&buf, &DATA1[..4]);
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_eq!(socket1.recv(&mut buf).unwrap(), (DATA2.len(), false));
+    assert_eq!(&buf[..DATA2.len()], DATA2);
+    assert_would_block(socket1.recv(&mut buf));
+
+    // Empty messages are preserved as well.
+    socket2.send(&[]).unwrap();
+    assert_eq!(socket1.recv(&mut buf).unwrap(), (0, false));
+
+    socket2.shutdown(Shutdown::Write).unwrap();
+    assert_eq!(socket1.recv(&mut buf).unwrap(), (0, false));
+    assert!(socket2.send(DATA1).is_err());
+    assert!(socket2.take_error().unwrap().is_none());
+}
+
+#[test]
+fn unix_seqpacket_raw_fd() {
+    let (socket1, socket2) = UnixSeqpacket::pair().unwrap();
+
+    let fd = socket1.into_raw_fd();
+    let socket1 = unsafe { UnixSeqpacket::from_raw_fd(fd) };
+
+    socket1.send(DATA1).unwrap();
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_eq!(socket2.recv(&mut buf).unwrap(), (DATA1.len(), false));
+    assert_eq!(&buf[..DATA1.len()], DATA1);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_seqpacket_abstract_addr() {
+    use mio::net::SocketAddr;
+
+    let name = format!("mio-unix_seqpacket_abstract_addr-{}", std::process::id());
+    let address = SocketAddr::from_abstract_name(&name).unwrap();
+
+    let listener = UnixSeqpacketListener::bind_addr(&address).unwrap();
+    let client = UnixSeqpacket::connect_addr(&address).unwrap();
+    assert_eq!(
+        client.peer_addr().unwrap().as_abstract_name(),
+        Some(name.as_bytes())
+    );
+    // Connecting to a Unix socket completes immediately, so there's no need
+    // to poll the listener.
+    let (server, _) = listener.accept().unwrap();
+
+    server.send(DATA1).unwrap();
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_eq!(client.recv(&mut buf).unwrap(), (DATA1.len(), false));
+}
@@ -0,0 +1,222 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-util"))]
+
+use mio::unix::{SigSet, Signals};
+use mio::{Interest, Token};
+use std::io;
+use std::mem;
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+use std::time::Duration;
+
+mod util;
+use util::{
+    assert_send, assert_sync, expect_events, expect_no_events, init, init_with_poll, ExpectEvent,
+    Readiness,
+};
+
+const TOKEN: Token = Token(0);
+
+#[test]
+fn signals_send_and_sync() {
+    assert_send::<Signals>();
+    assert_sync::<Signals>();
+}
+
+#[test]
+fn signals_invalid() {
+    init();
+
+    for &signal in &[0, -1, libc::SIGKILL, libc::SIGSTOP] {
+        let err = Signals::new(&[signal]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn signals_receive() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut signals = Signals::new(&[libc::SIGUSR1, libc::SIGUSR2]).unwrap();
+    poll.registry()
+        .register(&mut signals, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    assert!(signals.receive().unwrap().is_none());
+    expect_no_events(&mut poll, &mut events);
+
+    raise(libc::SIGUSR1);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let info = signals.receive().unwrap().unwrap();
+    assert_eq!(info.signal(), libc::SIGUSR1);
+    assert_eq!(info.pid(), unsafe { libc::getpid() });
+    assert_eq!(info.uid(), unsafe { libc::getuid() });
+    assert!(signals.receive().unwrap().is_none());
+
+    raise(libc::SIGUSR2);
+    raise(libc::SIGUSR1);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    let mut received: Vec<libc::c_int> = signals
+        .pending()
+        .map(|info| info.map(|info| info.signal()))
+        .collect::<io::Result<_>>()
+        .unwrap();
+    received.sort();
+    let mut expected = vec![libc::SIGUSR1, libc::SIGUSR2];
+    expected.sort();
+    assert_eq!(received, expected);
+
+    poll.registry().deregister(&mut signals).unwrap();
+}
+
+#[test]
+fn signals_restore_mask() {
+    let (poll, _) = init_with_poll();
+
+    // Already blocked signals must stay blocked.
+    block(libc::SIGUSR2);
+    assert!(!is_blocked(libc::SIGWINCH));
+
+    let mut signals = Signals::new(&[libc::SIGWINCH, libc::SIGUSR2]).unwrap();
+    poll.registry()
+        .register(&mut signals, TOKEN, Interest::READABLE)
+        .unwrap();
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert!(is_blocked(libc::SIGWINCH));
+
+    poll.registry().deregister(&mut signals).unwrap();
+    assert!(!is_blocked(libc::SIGWINCH));
+    assert!(is_blocked(libc::SIGUSR2));
+
+    // Dropping a registered `Signals` also restores the mask.
+    poll.registry()
+        .register(&mut signals, TOKEN, Interest::READABLE)
+        .unwrap();
+    drop(signals);
+    assert!(!is_blocked(libc::SIGWINCH));
+    assert!(is_blocked(libc::SIGUSR2));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn signals_restore_mask_other_thread() {
+    let (poll, _) = init_with_poll();
+    let registry = poll.registry().try_clone().unwrap();
+
+    let mut signals = Signals::new(&[libc::SIGURG]).unwrap();
+    // Blocks the signal for the spawned thread only.
+    let mut signals = std::thread::spawn(move || {
+        registry
+            .register(&mut signals, TOKEN, Interest::READABLE)
+            .unwrap();
+        assert!(is_blocked(libc::SIGURG));
+        signals
+    })
+    .join()
+    .unwrap();
+    assert!(!is_blocked(libc::SIGURG));
+
+    // The mask of this thread must not be changed.
+    block(libc::SIGURG);
+    assert!(poll.registry().deregister(&mut signals).is_err());
+    assert!(is_blocked(libc::SIGURG));
+}
+
+#[test]
+fn sigset() {
+    let mut set = SigSet::empty();
+    assert!(!set.contains(libc::SIGUSR1));
+    set.add(libc::SIGUSR1).unwrap();
+    set.add(libc::SIGUSR2).unwrap();
+    assert!(set.contains(libc::SIGUSR1));
+    assert!(set.contains(libc::SIGUSR2));
+    set.remove(libc::SIGUSR1).unwrap();
+    assert!(!set.contains(libc::SIGUSR1));
+    assert_eq!(format!("{:?}", set), format!("{{{}}}", libc::SIGUSR2));
+
+    assert!(SigSet::full().contains(libc::SIGUSR1));
+    assert_eq!(set.add(-1).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    block(libc::SIGUSR2);
+    assert!(SigSet::current().unwrap().contains(libc::SIGUSR2));
+}
+
+#[test]
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+fn poll_with_sigmask() {
+    static RECEIVED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn handler(_: libc::c_int) {
+        RECEIVED.store(true, Ordering::SeqCst);
+    }
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut action: libc::sigaction = unsafe { mem::zeroed() };
+    action.sa_sigaction = handler as extern "C" fn(libc::c_int) as usize;
+    assert_eq!(
+        unsafe { libc::sigaction(libc::SIGURG, &action, std::ptr::null_mut()) },
+        0
+    );
+
+    // Signal is pending, but blocked.
+    block(libc::SIGURG);
+    raise(libc::SIGURG);
+    assert!(!RECEIVED.load(Ordering::SeqCst));
+
+    // Without unblocking the signal, the poll simply times out.
+    let sigmask = SigSet::current().unwrap();
+    for timeout in &[Duration::from_millis(1), Duration::from_micros(100)] {
+        poll.poll_with_sigmask(&mut events, Some(*timeout), &sigmask)
+            .unwrap();
+        assert!(events.is_empty());
+    }
+    assert!(!RECEIVED.load(Ordering::SeqCst));
+
+    // The signal is unblocked while polling, interrupting it.
+    let mut sigmask = SigSet::current().unwrap();
+    sigmask.remove(libc::SIGURG).unwrap();
+    let err = poll
+        .poll_with_sigmask(&mut events, None, &sigmask)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    assert!(RECEIVED.load(Ordering::SeqCst));
+    // And the original mask is restored.
+    assert!(is_blocked(libc::SIGURG));
+}
+
+fn raise(signal: libc::c_int) {
+    // Sends the signal to the calling thread.
+    assert_eq!(unsafe { libc::raise(signal) }, 0);
+}
+
+fn block(signal: libc::c_int) {
+    let mut set: libc::sigset_t = unsafe { mem::zeroed() };
+    unsafe {
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, signal);
+        assert_eq!(
+            libc::pthread_sigmask(libc::SIG_BLOCK, &set, std::ptr::null_mut()),
+            0
+        );
+    }
+}
+
+fn is_blocked(signal: libc::c_int) -> bool {
+    let mut set: libc::sigset_t = unsafe { mem::zeroed() };
+    unsafe {
+        assert_eq!(
+            libc::pthread_sigmask(libc::SIG_BLOCK, std::ptr::null(), &mut set),
+            0
+        );
+        libc::sigismember(&set, signal) == 1
+    }
+}
@@ -4,6 +4,7 @@ use mio::net::UnixStream;
 use mio::{Interest, Token};
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::Shutdown;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::os::unix::net;
 use std::path::Path;
 use std::sync::mpsc::channel;
@@ -345,6 +346,78 @@ fn unix_stream_shutdown_listener_write() {
     handle.join().unwrap();
 }
 
+#[test]
+fn unix_stream_pass_fds() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, s2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut s1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    // Pass one end of a standard socket pair.
+    let (passed, other) = net::UnixStream::pair().unwrap();
+    assert_eq!(
+        s2.send_with_fds(DATA1, &[passed.as_raw_fd()]).unwrap(),
+        DATA1_LEN
+    );
+    drop(passed);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, fds) = s1.recv_with_fds(&mut buf, 4).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert!(!fds.is_truncated());
+    assert_eq!(fds.len(), 1);
+    let fd = fds.into_vec().pop().unwrap();
+    assert_socket_close_on_exec(&fd);
+
+    // The received file descriptor refers to the same socket.
+    let mut received = unsafe { net::UnixStream::from_raw_fd(fd.into_raw_fd()) };
+    received.write_all(DATA2).unwrap();
+    let mut buf = [0; DATA2_LEN];
+    (&other).read_exact(&mut buf).unwrap();
+    assert_eq!(buf, DATA2);
+
+    // Sending more file descriptors than asked for truncates the control
+    // data.
+    let (passed1, passed2) = net::UnixStream::pair().unwrap();
+    s2.send_with_fds(DATA2, &[passed1.as_raw_fd(), passed2.as_raw_fd()])
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, fds) = s1.recv_with_fds(&mut buf, 1).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert!(fds.is_truncated());
+    assert_eq!(fds.len(), 1);
+
+    assert_would_block(s1.recv_with_fds(&mut buf, 1));
+}
+
+#[test]
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+fn unix_stream_peer_cred() {
+    let (s1, s2) = UnixStream::pair().unwrap();
+
+    let cred = s1.peer_cred().unwrap();
+    assert_eq!(cred.uid(), unsafe { libc::geteuid() });
+    assert_eq!(cred.gid(), unsafe { libc::getegid() });
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(cred.pid(), Some(unsafe { libc::getpid() }));
+    assert_eq!(s2.peer_cred().unwrap(), cred);
+}
+
 #[test]
 fn unix_stream_register() {
     let (mut poll, mut events) = init_with_poll();
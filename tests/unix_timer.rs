@@ -0,0 +1,207 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "os-util"
+))]
+
+use mio::unix::{Clock, Timer};
+use mio::{Interest, Token};
+use std::io;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod util;
+use util::{
+    assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
+    assert_would_block, expect_events, expect_no_events, init, init_with_poll, ExpectEvent,
+    Readiness,
+};
+
+const TOKEN: Token = Token(0);
+
+#[test]
+fn timer_send_and_sync() {
+    assert_send::<Timer>();
+    assert_sync::<Timer>();
+}
+
+#[test]
+fn timer_flags() {
+    init();
+
+    let timer = Timer::new(Clock::Monotonic).unwrap();
+    assert_socket_non_blocking(&timer);
+    assert_socket_close_on_exec(&timer);
+}
+
+#[test]
+fn timer_timeout() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut timer = Timer::new(Clock::Monotonic).unwrap();
+    poll.registry()
+        .register(&mut timer, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    assert_would_block(timer.read());
+    expect_no_events(&mut poll, &mut events);
+
+    timer.set_timeout(Duration::from_millis(10)).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    assert_eq!(timer.read().unwrap(), 1);
+    assert_eq!(timer.remaining().unwrap(), None);
+
+    // One-shot timers only expire once.
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(timer.read());
+}
+
+#[test]
+fn timer_zero_timeout() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut timer = Timer::new(Clock::Monotonic).unwrap();
+    poll.registry()
+        .register(&mut timer, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    timer.set_timeout(Duration::from_secs(0)).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    assert_eq!(timer.read().unwrap(), 1);
+}
+
+#[test]
+fn timer_deadline() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut timer = Timer::new(Clock::Realtime).unwrap();
+    poll.registry()
+        .register(&mut timer, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    timer.set_deadline(now + Duration::from_millis(10)).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    assert_eq!(timer.read().unwrap(), 1);
+
+    // A deadline in the past expires immediately.
+    timer.set_deadline(now).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    assert_eq!(timer.read().unwrap(), 1);
+}
+
+#[test]
+fn timer_interval() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut timer = Timer::new(Clock::Boottime).unwrap();
+    poll.registry()
+        .register(&mut timer, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    timer.set_interval(Duration::from_millis(5)).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    assert!(timer.read().unwrap() >= 1);
+
+    // Periodic timers keep expiring.
+    sleep(Duration::from_millis(50));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    assert!(timer.read().unwrap() >= 2);
+    assert!(timer.remaining().unwrap().is_some());
+
+    timer.disarm().unwrap();
+    let _ = timer.read();
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+fn timer_interval_at() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut timer = Timer::new(Clock::Realtime).unwrap();
+    poll.registry()
+        .register(&mut timer, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    timer
+        .set_interval_at(now + Duration::from_millis(10), Duration::from_secs(60))
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN, Readiness::READABLE)],
+    );
+    assert_eq!(timer.read().unwrap(), 1);
+    let remaining = timer.remaining().unwrap().unwrap();
+    assert!(remaining > Duration::from_secs(50) && remaining <= Duration::from_secs(60));
+}
+
+#[test]
+fn timer_zero_interval() {
+    init();
+
+    let timer = Timer::new(Clock::Monotonic).unwrap();
+    let err = timer.set_interval(Duration::from_secs(0)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn timer_disarm() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut timer = Timer::new(Clock::Monotonic).unwrap();
+    poll.registry()
+        .register(&mut timer, TOKEN, Interest::READABLE)
+        .unwrap();
+
+    timer.set_timeout(Duration::from_millis(20)).unwrap();
+    let remaining = timer.remaining().unwrap().unwrap();
+    assert!(remaining <= Duration::from_millis(20));
+
+    timer.disarm().unwrap();
+    assert_eq!(timer.remaining().unwrap(), None);
+    sleep(Duration::from_millis(30));
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(timer.read());
+}
+
+#[test]
+fn timer_deregister() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut timer = Timer::new(Clock::Monotonic).unwrap();
+    poll.registry()
+        .register(&mut timer, TOKEN, Interest::READABLE)
+        .unwrap();
+    poll.registry().deregister(&mut timer).unwrap();
+
+    timer.set_timeout(Duration::from_millis(1)).unwrap();
+    sleep(Duration::from_millis(10));
+    expect_no_events(&mut poll, &mut events);
+    assert_eq!(timer.read().unwrap(), 1);
+}